@@ -1,7 +1,13 @@
-use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::fs;
 use std::io::Cursor;
 use std::sync::OnceLock;
-use word_tally::{Filters, MinChars, Options, Sort, WordTally};
+use word_tally::{
+    word_spans, Case, ExcludeWords, Filters, MinChars, MinCount, Options, Sort, WordTally,
+};
+
+/// A public-domain corpus downloaded by `benches/fetch-corpus.sh`, if present.
+const CORPUS_PATH: &str = "benches/data/corpus.txt";
 
 const INPUT: &str = "Orchids bloom silently\nMicrocontrollers hum\nPhalaenopsis thrives\n\
     Data packets route\nPhalaenopsis BLOOM\nDendrobium anchors\nPhotosynthesis proceeds\n\
@@ -11,6 +17,7 @@ const INPUT: &str = "Orchids bloom silently\nMicrocontrollers hum\nPhalaenopsis
     DATA packets route\nPhalaenopsis BLOOM\nOrchids in DATA streams";
 
 static INPUT_LOCK: OnceLock<String> = OnceLock::new();
+static SYNTHETIC_LOCK: OnceLock<String> = OnceLock::new();
 
 fn repeated_input() -> &'static String {
     INPUT_LOCK.get_or_init(|| INPUT.repeat(42))
@@ -21,6 +28,46 @@ fn prepare_input() -> Cursor<&'static str> {
     Cursor::new(input)
 }
 
+/// Generates a corpus with a Zipf-like spread of word frequencies, the same on every run.
+fn synthetic_input() -> &'static String {
+    SYNTHETIC_LOCK.get_or_init(|| {
+        const SYLLABLES: [&str; 12] = [
+            "ka", "lo", "mi", "ne", "su", "ta", "ri", "wo", "ba", "de", "gu", "po",
+        ];
+        let mut state: u64 = 42;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            (state >> 33) as usize
+        };
+
+        let mut corpus = String::new();
+        for line in 0..2_000 {
+            for _ in 0..12 {
+                let rank = 1 + next() % 1_000;
+                let id = 1_000 / rank + next() % rank;
+                let mut word = String::new();
+                for digit in [id % 12, id / 12 % 12, id / 144 % 12] {
+                    word.push_str(SYLLABLES[digit]);
+                }
+                if next() % 10 == 0 {
+                    word = word.to_uppercase();
+                }
+                corpus.push_str(&word);
+                corpus.push(' ');
+            }
+            corpus.push(if line % 7 == 0 { '.' } else { '\n' });
+        }
+
+        corpus
+    })
+}
+
+fn prepare_synthetic() -> Cursor<&'static str> {
+    Cursor::new(synthetic_input())
+}
+
 fn bench_new_unsorted(c: &mut Criterion) {
     c.bench_function("new_unsorted", |b| {
         b.iter_batched(
@@ -92,7 +139,10 @@ fn bench_new_min_count(c: &mut Criterion) {
                         sort: Sort::Unsorted,
                         ..Options::default()
                     },
-                    Filters::default(),
+                    Filters {
+                        min_count: Some(MinCount(2)),
+                        ..Filters::default()
+                    },
                 )
             },
             BatchSize::SmallInput,
@@ -119,6 +169,72 @@ fn bench_sort(c: &mut Criterion) {
     });
 }
 
+fn bench_word_spans(c: &mut Criterion) {
+    c.bench_function("word_spans", |b| {
+        let input = repeated_input();
+        let mut spans = Vec::new();
+        b.iter(|| {
+            for line in input.lines() {
+                word_spans(line, &mut spans);
+            }
+        });
+    });
+}
+
+fn bench_cases(c: &mut Criterion) {
+    let mut group = c.benchmark_group("case");
+    for case in [Case::Original, Case::Lower, Case::Upper] {
+        group.bench_with_input(BenchmarkId::from_parameter(case), &case, |b, &case| {
+            b.iter_batched(
+                prepare_synthetic,
+                |input| WordTally::new(input, Options::new(case, Sort::Desc), Filters::default()),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_filters(c: &mut Criterion) {
+    let exclude = ExcludeWords(vec!["kaloka".to_string(), "mimimi".to_string()]);
+    let filters = [
+        ("none", Filters::default()),
+        ("min_chars", Filters::new(&Some(7), &None, None)),
+        ("min_count", Filters::new(&None, &Some(3), None)),
+        (
+            "exclude",
+            Filters::new(&None, &None, Some(exclude.0.clone())),
+        ),
+        ("all", Filters::new(&Some(7), &Some(3), Some(exclude.0))),
+    ];
+
+    let mut group = c.benchmark_group("filters");
+    for (name, filters) in filters {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &filters, |b, filters| {
+            b.iter_batched(
+                prepare_synthetic,
+                |input| WordTally::new(input, Options::default(), filters.clone()),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_corpus(c: &mut Criterion) {
+    let Ok(corpus) = fs::read_to_string(CORPUS_PATH) else {
+        return;
+    };
+
+    c.bench_function("corpus", |b| {
+        b.iter_batched(
+            || Cursor::new(corpus.as_str()),
+            |input| WordTally::new(input, Options::default(), Filters::default()),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
 fn configure_criterion() -> Criterion {
     Criterion::default().noise_threshold(0.1)
 }
@@ -126,6 +242,6 @@ fn configure_criterion() -> Criterion {
 criterion_group! {
     name = benches;
     config = configure_criterion();
-    targets = bench_new_unsorted, bench_new_sorted, bench_new_min_chars, bench_new_min_count, bench_sort
+    targets = bench_new_unsorted, bench_new_sorted, bench_new_min_chars, bench_new_min_count, bench_sort, bench_word_spans, bench_cases, bench_filters, bench_corpus
 }
 criterion_main!(benches);
@@ -0,0 +1,10 @@
+#!/bin/sh
+# Downloads a public-domain corpus for the `corpus` benchmark.
+set -eu
+
+url="${1:-https://www.gutenberg.org/cache/epub/2600/pg2600.txt}"
+dir="$(dirname "$0")/data"
+
+mkdir -p "$dir"
+curl --fail --location --silent --show-error --output "$dir/corpus.txt" "$url"
+echo "Saved $url to $dir/corpus.txt"
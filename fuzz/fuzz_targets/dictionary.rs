@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use word_tally::Dictionary;
+
+// The input is an `.aff` file, a NUL byte, a `.dic` file, a NUL byte and words to stem.
+fuzz_target!(|text: &str| {
+    let mut parts = text.splitn(3, '\0');
+    let (Some(aff), Some(dic), Some(words)) = (parts.next(), parts.next(), parts.next()) else {
+        return;
+    };
+
+    if let Ok(dictionary) = Dictionary::parse(aff, dic) {
+        for word in words.split_whitespace() {
+            let _stem = dictionary.stem(word);
+        }
+    }
+});
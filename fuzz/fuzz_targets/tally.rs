@@ -0,0 +1,43 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use word_tally::{
+    Case, Filters, LengthUnit, Lexicon, MinChars, MinCount, Options, Phrases, Sort, WordTally,
+};
+
+// The first two bytes choose options and filters, and the rest is the input.
+fuzz_target!(|data: &[u8]| {
+    let [flags, limit, input @ ..] = data else {
+        return;
+    };
+
+    let case = [Case::Original, Case::Upper, Case::Lower][usize::from(flags % 3)];
+    let sort = [Sort::Desc, Sort::Asc, Sort::Unsorted][usize::from(flags / 3 % 3)];
+    let options = Options::new(case, sort)
+        .with_fold_possessives(flags & 0x10 != 0)
+        .with_fuzzy_merge((flags & 0x20 != 0).then_some(usize::from(limit % 3)));
+
+    let unit = [LengthUnit::Bytes, LengthUnit::Chars, LengthUnit::Graphemes];
+    let filters = Filters {
+        min_chars: (flags & 0x40 != 0).then_some(MinChars(usize::from(limit % 8))),
+        min_count: (flags & 0x80 != 0).then_some(MinCount(usize::from(limit % 4))),
+        ..Filters::default()
+    }
+    .with_length_unit(unit[usize::from(limit % 3)]);
+
+    let lexicon = if flags & 0x08 == 0 {
+        Lexicon::default()
+    } else {
+        let text = String::from_utf8_lossy(input);
+        let phrases = text.split_whitespace().collect::<Vec<_>>();
+        Lexicon::default().with_phrases(Phrases::new(phrases.windows(2).map(|pair| pair.join(" "))))
+    };
+
+    let tally = WordTally::with_lexicon(input, options, filters, &lexicon);
+
+    assert_eq!(tally.uniq_count(), tally.tally().len());
+    assert_eq!(
+        tally.count(),
+        tally.tally().iter().map(|&(_, count)| count).sum::<usize>()
+    );
+});
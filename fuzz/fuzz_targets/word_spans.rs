@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use word_tally::word_spans;
+
+fuzz_target!(|text: &str| {
+    let mut spans = Vec::new();
+    word_spans(text, &mut spans);
+
+    let mut end = 0;
+    for span in &spans {
+        assert!(span.offset >= end, "Spans are ordered and don't overlap.");
+        assert!(span.len > 0, "Spans aren't empty.");
+        assert!(!span.word(text).is_empty());
+        end = span.range().end;
+    }
+    assert!(end <= text.len());
+});
@@ -1,6 +1,10 @@
-use clap::Parser;
+use crate::exit_code::ExitCodes;
+use crate::verbose::Verbosity;
+use clap::{ArgAction, Parser, ValueEnum};
+use regex::Regex;
+use std::ops::{Range, RangeInclusive};
 use std::path::PathBuf;
-use word_tally::{Case, Sort};
+use word_tally::{Case, Denoise, Encoding, LengthUnit, Segmenter, Sort, Unit, WordType};
 
 #[derive(Debug, Parser)]
 #[command(about, version)]
@@ -9,35 +13,344 @@ pub struct Args {
     #[arg(default_value = "-", value_name = "PATH")]
     pub input: String,
 
+    /// Read NUL- or newline-delimited input paths from a file or stdin ("-").
+    #[arg(long, value_name = "PATH", conflicts_with = "input")]
+    pub files0_from: Option<String>,
+
+    /// Tally only words starting at or after this byte offset of UTF-8 input.
+    #[arg(long, default_value_t, value_name = "BYTES")]
+    pub byte_offset: u64,
+
+    /// Tally only words starting within this many bytes of the byte offset.
+    #[arg(long, value_name = "BYTES")]
+    pub byte_limit: Option<u64>,
+
     /// Sort order.
     #[arg(short, long, default_value_t, value_enum, value_name = "ORDER")]
     pub sort: Sort,
 
+    /// Reverse the output order after sorting.
+    #[arg(long)]
+    pub reverse: bool,
+
+    /// Skip the first words of the output.
+    #[arg(long, default_value_t, value_name = "COUNT")]
+    pub skip: usize,
+
+    /// Limit the output to at most this many words.
+    #[arg(long, value_name = "COUNT")]
+    pub limit: Option<usize>,
+
+    /// Tally each document separated by lines exactly matching this separator.
+    #[arg(
+        long,
+        value_name = "LINE",
+        conflicts_with_all = [
+            "rank_range",
+            "suggest_min_count",
+            "group_by",
+            "top_per",
+            "verbose",
+            "verbose_output",
+            "readability",
+        ]
+    )]
+    pub doc_separator: Option<String>,
+
+    /// Print only the distinct words, alphabetically unless unsorted.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "min_count",
+            "rank_range",
+            "suggest_min_count",
+            "fuzzy_merge",
+            "baseline",
+            "normalize_counts",
+            "with_script",
+            "with_syllables",
+            "align",
+            "group_by",
+            "top_per",
+            "verbose",
+            "verbose_output",
+            "doc_separator",
+            "readability",
+        ]
+    )]
+    pub unique: bool,
+
     /// Case normalization.
     #[arg(short, long, default_value_t, value_enum, value_name = "FORMAT")]
     pub case: Case,
 
+    /// Text encoding of the input.
+    #[arg(long, default_value_t, value_enum, value_name = "ENCODING")]
+    pub encoding: Encoding,
+
+    /// How text is segmented into words.
+    #[arg(long, default_value_t, value_enum, value_name = "SEGMENTER")]
+    pub segmenter: Segmenter,
+
+    /// Count the sentences or paragraphs a word appears in, not its occurrences.
+    #[arg(
+        long,
+        default_value_t,
+        value_enum,
+        value_name = "UNIT",
+        conflicts_with = "fuzzy_merge"
+    )]
+    pub unit: Unit,
+
+    /// Fail if more bytes than this would be buffered at once, like a long line, paragraph or document.
+    #[arg(long, value_name = "BYTES")]
+    pub max_buffer: Option<usize>,
+
+    /// Strip possessive "'s" endings so they tally with the base word.
+    #[arg(long)]
+    pub fold_possessives: bool,
+
     /// Exclude words containing fewer than min chars.
     #[arg(short, long, value_name = "COUNT")]
     pub min_chars: Option<usize>,
 
+    /// Unit for measuring word length with min chars.
+    #[arg(long, default_value_t, value_enum, value_name = "UNIT")]
+    pub length_unit: LengthUnit,
+
+    /// Exclude words with fewer than min estimated syllables.
+    #[arg(long, value_name = "COUNT")]
+    pub min_syllables: Option<usize>,
+
     /// Exclude words appearing fewer than min times.
     #[arg(short = 'M', long, value_name = "COUNT")]
     pub min_count: Option<usize>,
 
+    /// Keep only words ranked within an inclusive range, like "100..1000".
+    #[arg(long, value_parser = parse_rank_range, value_name = "RANGE")]
+    pub rank_range: Option<RangeInclusive<usize>>,
+
+    /// Print a suggested min count at the knee of the word frequencies.
+    #[arg(long)]
+    pub suggest_min_count: bool,
+
+    /// Print the average sentence length and Flesch-Kincaid and SMOG grades.
+    #[arg(long)]
+    pub readability: bool,
+
     /// Exclude words from a comma-delimited list.
     #[arg(short, long, use_value_delimiter = true, value_name = "WORDS")]
     pub exclude: Option<Vec<String>>,
 
+    /// Exclude words listed one per line in a file.
+    #[arg(long, value_name = "PATH")]
+    pub exclude_from: Option<PathBuf>,
+
+    /// Include only kinds of words from a comma-delimited list.
+    #[arg(long, use_value_delimiter = true, value_enum, value_name = "TYPES")]
+    pub word_types: Option<Vec<WordType>>,
+
+    /// Exclude tokens that look like noise rather than words.
+    #[arg(long, value_enum, value_name = "KIND")]
+    pub denoise: Option<Denoise>,
+
+    /// Tally multi-word phrases, one per line in a file, as single words.
+    #[arg(long, value_name = "PATH")]
+    pub phrases: Option<PathBuf>,
+
+    /// Fold words to stems from a Hunspell dictionary (e.g. "en_US").
+    #[cfg(feature = "hunspell")]
+    #[arg(long, value_name = "PATH")]
+    pub hunspell: Option<PathBuf>,
+
+    /// Merge words within a max edit distance, from 1 to 3, of a more common word, at most one edit per 4 chars.
+    #[arg(long, value_parser = parse_fuzzy_merge, value_name = "DISTANCE")]
+    pub fuzzy_merge: Option<usize>,
+
+    /// Include a keyness column comparing against a "word,count" frequency list.
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Output counts relative to the total number of words.
+    #[arg(long, value_enum, value_name = "UNIT")]
+    pub normalize_counts: Option<Normalize>,
+
+    /// Decimal places for normalized counts, keyness and readability scores.
+    #[arg(long, default_value_t = 2, value_name = "DIGITS")]
+    pub precision: usize,
+
+    /// Tally the text at a dotted field path of each JSON Lines record.
+    #[cfg(feature = "serde")]
+    #[arg(long, value_name = "FIELD")]
+    pub json_field: Option<String>,
+
+    /// Read tallying options from a JSON file instead of the options flags.
+    #[cfg(feature = "serde")]
+    #[arg(long, value_name = "PATH")]
+    pub options_in: Option<PathBuf>,
+
+    /// Write the resolved tallying options to a JSON file.
+    #[cfg(feature = "serde")]
+    #[arg(long, value_name = "PATH")]
+    pub options_out: Option<PathBuf>,
+
     /// Delimiter between keys and values.
     #[arg(short, long, default_value = " ", value_name = "VALUE")]
     pub delimiter: String,
 
+    /// Include script and direction columns for each word.
+    #[arg(long)]
+    pub with_script: bool,
+
+    /// Include a column of estimated syllables for each word.
+    #[arg(long)]
+    pub with_syllables: bool,
+
+    /// Pad columns so they line up.
+    #[arg(long)]
+    pub align: bool,
+
+    /// Truncate words wider than this in the output with an ellipsis.
+    #[arg(long, value_name = "WIDTH")]
+    pub max_word_display: Option<usize>,
+
+    /// Output a separate tally for each group.
+    #[arg(long, value_enum, value_name = "GROUP")]
+    pub group_by: Option<GroupBy>,
+
+    /// Output the top words for each group matched by a regex, like "^.=5".
+    #[arg(long, value_parser = parse_top_per, value_name = "PATTERN=COUNT", conflicts_with = "group_by")]
+    pub top_per: Option<TopPer>,
+
+    /// Succeed, warn or fail when no words are tallied.
+    #[arg(long, default_value_t, value_enum, value_name = "ACTION")]
+    pub empty: Empty,
+
+    /// Exit codes for errors, either BSD sysexits or 1 for any error.
+    #[arg(long, default_value_t, value_enum, value_name = "SCHEME")]
+    pub exit_codes: ExitCodes,
+
     /// Write output to file rather than stdout.
     #[arg(short, long, value_name = "PATH")]
     pub output: Option<PathBuf>,
 
-    /// Print verbose details.
-    #[arg(short, long)]
-    pub verbose: bool,
+    /// Print verbose details, repeated for more detail.
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Suppress non-essential output to stderr.
+    #[arg(long, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Write verbose details to file rather than stderr.
+    #[arg(long, value_name = "PATH")]
+    pub verbose_output: Option<PathBuf>,
+}
+
+impl Args {
+    /// The level of detail for stderr output.
+    pub const fn verbosity(&self) -> Verbosity {
+        match (self.quiet, self.verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+
+    /// The byte range of the input to tally, if it's limited to one.
+    pub fn byte_range(&self) -> Option<Range<u64>> {
+        let end = self
+            .byte_limit
+            .map_or(u64::MAX, |limit| self.byte_offset.saturating_add(limit));
+
+        (self.byte_offset > 0 || self.byte_limit.is_some()).then_some(self.byte_offset..end)
+    }
+}
+
+/// Actions for input with no words to tally.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Empty {
+    #[default]
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// Units for counts relative to the total number of words.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Normalize {
+    PerThousand,
+    PerMillion,
+}
+
+impl Normalize {
+    /// Gets a count per unit of the `total` number of words.
+    pub fn per(self, count: usize, total: usize) -> f64 {
+        let scale = match self {
+            Self::PerThousand => 1_000.0,
+            Self::PerMillion => 1_000_000.0,
+        };
+
+        count as f64 * scale / total as f64
+    }
+}
+
+/// Groupings for outputting separate tallies.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum GroupBy {
+    Script,
+}
+
+/// The top words to output for each group of words a pattern matches.
+#[derive(Clone, Debug)]
+pub struct TopPer {
+    /// Pattern whose first capture group, or whole match, names a word's group.
+    pub pattern: Regex,
+
+    /// Number of top words to output for each group.
+    pub count: usize,
+}
+
+/// Parses a pattern and count separated by a final "=".
+fn parse_top_per(top_per: &str) -> Result<TopPer, String> {
+    let (pattern, count) = top_per
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected a pattern and count like \"^.=5\", got \"{top_per}\""))?;
+    let pattern = Regex::new(pattern).map_err(|err| err.to_string())?;
+    let count = count
+        .parse()
+        .map_err(|_| format!("invalid count: \"{count}\""))?;
+
+    Ok(TopPer { pattern, count })
+}
+
+/// Parses a fuzzy merge edit distance, capped since the deletion index grows combinatorially with it.
+fn parse_fuzzy_merge(distance: &str) -> Result<usize, String> {
+    match distance.parse() {
+        Ok(distance @ 1..=3) => Ok(distance),
+        _ => Err(format!(
+            "expected a distance from 1 to 3, got \"{distance}\""
+        )),
+    }
+}
+
+/// Parses an inclusive range of ranks counting from 1, where either end may be omitted.
+fn parse_rank_range(range: &str) -> Result<RangeInclusive<usize>, String> {
+    let (start, end) = range
+        .split_once("..")
+        .ok_or_else(|| format!("expected a range like \"100..1000\", got \"{range}\""))?;
+    let parse = |rank: &str, default| match rank {
+        "" => Ok(default),
+        rank => rank
+            .parse()
+            .map_err(|_| format!("invalid rank: \"{rank}\"")),
+    };
+    let (start, end) = (parse(start, 1)?, parse(end, usize::MAX)?);
+
+    if start == 0 || start > end {
+        return Err(format!("invalid rank range: \"{range}\""));
+    }
+
+    Ok(start..=end)
 }
@@ -0,0 +1,97 @@
+use crate::{Case, WordTallyError};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, ErrorKind::InvalidData};
+use std::path::Path;
+
+/// A reference frequency list for comparing a tally against a baseline corpus.
+///
+/// Keyness is the signed log-likelihood (G²) of a word's count in a tally
+/// against its count in the baseline. Positive scores mark words used more
+/// than the baseline predicts and negative scores words used less.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    /// Reference counts keyed by case-normalized word.
+    counts: HashMap<Box<str>, usize>,
+
+    /// The sum of all reference counts.
+    total: usize,
+}
+
+impl Baseline {
+    /// Reads a baseline from a file of `word,count` lines.
+    pub fn open(path: &Path, case: Case) -> Result<Self, WordTallyError> {
+        Self::parse(&fs::read_to_string(path)?, case)
+    }
+
+    /// Parses `word,count` lines, skipping blank lines, `#` comment lines and a header line.
+    ///
+    /// The header is the first line that isn't blank or a comment, if its count isn't a number.
+    /// Words are normalized with the `Case` of the tally they're compared to.
+    pub fn parse(csv: &str, case: Case) -> Result<Self, WordTallyError> {
+        let mut baseline = Self::default();
+        let lines = csv
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        for (index, line) in lines.enumerate() {
+            let (word, count) = line
+                .rsplit_once([',', '\t'])
+                .ok_or_else(|| io::Error::new(InvalidData, format!("Invalid line: {line}")))?;
+            let count = match count.trim().parse::<usize>() {
+                Ok(count) => count,
+                Err(_) if index == 0 => continue,
+                Err(_) => {
+                    return Err(
+                        io::Error::new(InvalidData, format!("Invalid count: {line}")).into(),
+                    )
+                }
+            };
+
+            *baseline
+                .counts
+                .entry(case.normalize(word.trim()))
+                .or_insert(0) += count;
+            baseline.total += count;
+        }
+
+        Ok(baseline)
+    }
+
+    /// Gets the reference count for a word, or zero if it isn't in the baseline.
+    pub fn get(&self, word: &str) -> usize {
+        self.counts.get(word).copied().unwrap_or_default()
+    }
+
+    /// Gets the sum of all reference counts.
+    pub const fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Scores a word's `count` out of a tally's `total` against the baseline.
+    pub fn keyness(&self, word: &str, count: usize, total: usize) -> f64 {
+        let observed = count as f64;
+        let reference = self.get(word) as f64;
+        let (total, reference_total) = (total as f64, self.total as f64);
+        let expected = total * (observed + reference) / (total + reference_total);
+        let reference_expected =
+            reference_total * (observed + reference) / (total + reference_total);
+
+        let g2 = 2.0 * (Self::term(observed, expected) + Self::term(reference, reference_expected));
+        if observed / total < reference / reference_total {
+            -g2
+        } else {
+            g2
+        }
+    }
+
+    /// A log-likelihood term, where a zero count contributes nothing.
+    fn term(observed: f64, expected: f64) -> f64 {
+        if observed > 0.0 {
+            observed * (observed / expected).ln()
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::args::Normalize;
+use crate::output::Output;
+use crate::width;
+use anyhow::Result;
+use word_tally::{syllables, Baseline, Script};
+
+/// The columns written for each word, its count and any optional details.
+pub struct Columns<'a> {
+    /// Delimiter between columns.
+    delimiter: &'a str,
+
+    /// Whether to include script and direction columns.
+    with_script: bool,
+
+    /// Whether to include an estimated syllables column.
+    with_syllables: bool,
+
+    /// Baseline to score the keyness of each word against.
+    baseline: Option<&'a Baseline>,
+
+    /// The sum of all words tallied, for scoring keyness and normalizing counts.
+    total: usize,
+
+    /// Whether to pad columns to the width of their widest cell.
+    align: bool,
+
+    /// Relative frequency to write instead of each raw count.
+    normalize: Option<Normalize>,
+
+    /// Decimal places for normalized counts and keyness scores.
+    precision: usize,
+
+    /// Widest a word is displayed before it's truncated with an ellipsis.
+    max_word_width: Option<usize>,
+}
+
+/// Which side of a padded column a cell is aligned to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+}
+
+impl<'a> Columns<'a> {
+    /// Constructs the `Columns` for a tally of `total` words.
+    pub const fn new(delimiter: &'a str, total: usize) -> Self {
+        Self {
+            delimiter,
+            with_script: false,
+            with_syllables: false,
+            baseline: None,
+            total,
+            align: false,
+            normalize: None,
+            precision: 2,
+            max_word_width: None,
+        }
+    }
+
+    /// Sets whether to include script and direction columns.
+    pub const fn with_script(self, with_script: bool) -> Self {
+        Self {
+            with_script,
+            ..self
+        }
+    }
+
+    /// Sets whether to include an estimated syllables column.
+    pub const fn with_syllables(self, with_syllables: bool) -> Self {
+        Self {
+            with_syllables,
+            ..self
+        }
+    }
+
+    /// Sets the baseline to score keyness against.
+    pub const fn with_baseline(self, baseline: Option<&'a Baseline>) -> Self {
+        Self { baseline, ..self }
+    }
+
+    /// Sets whether to pad columns so they line up.
+    pub const fn with_align(self, align: bool) -> Self {
+        Self { align, ..self }
+    }
+
+    /// Sets the relative frequency to write instead of raw counts.
+    pub const fn with_normalize(self, normalize: Option<Normalize>) -> Self {
+        Self { normalize, ..self }
+    }
+
+    /// Sets the decimal places for normalized counts and keyness scores.
+    pub const fn with_precision(self, precision: usize) -> Self {
+        Self { precision, ..self }
+    }
+
+    /// Sets the widest a word is displayed before it's truncated.
+    pub const fn with_max_word_width(self, max_word_width: Option<usize>) -> Self {
+        Self {
+            max_word_width,
+            ..self
+        }
+    }
+
+    /// Writes a line for each word and its count, with any optional columns.
+    pub fn write(&self, output: &mut Output, tally: &[(Box<str>, usize)]) -> Result<()> {
+        let rows: Vec<_> = tally
+            .iter()
+            .map(|(word, count)| self.cells(word, *count))
+            .collect();
+        let widths = if self.align {
+            Self::widths(&rows)
+        } else {
+            Vec::new()
+        };
+
+        for row in &rows {
+            let mut line = String::new();
+            for (index, (cell, align)) in row.iter().enumerate() {
+                if index > 0 {
+                    line.push_str(self.delimiter);
+                }
+                let padding = widths
+                    .get(index)
+                    .map_or(0, |width| width - Self::width(cell));
+                let is_last = index + 1 == row.len();
+                match align {
+                    Align::Left if !is_last => line.extend([cell.as_str(), &" ".repeat(padding)]),
+                    Align::Left => line.push_str(cell),
+                    Align::Right => line.extend([&" ".repeat(padding), cell.as_str()]),
+                }
+            }
+            line.push('\n');
+            output.write_line(&line)?;
+        }
+
+        Ok(())
+    }
+
+    /// The cells of a row for a word and its count.
+    fn cells(&self, word: &str, count: usize) -> Vec<(String, Align)> {
+        let count_cell = self.normalize.map_or_else(
+            || count.to_string(),
+            |normalize| {
+                let frequency = normalize.per(count, self.total);
+                format!("{frequency:.precision$}", precision = self.precision)
+            },
+        );
+        let mut cells = vec![
+            (self.display_word(word), Align::Left),
+            (count_cell, Align::Right),
+        ];
+        if self.with_script {
+            let script = Script::of(word);
+            cells.push((script.to_string(), Align::Left));
+            cells.push((script.direction().to_string(), Align::Left));
+        }
+        if self.with_syllables {
+            cells.push((syllables(word).to_string(), Align::Right));
+        }
+        if let Some(baseline) = self.baseline {
+            let keyness = baseline.keyness(word, count, self.total);
+            let keyness = format!("{keyness:.precision$}", precision = self.precision);
+            cells.push((keyness, Align::Right));
+        }
+
+        cells
+    }
+
+    /// A word as displayed, truncated with an ellipsis if it's wider than the max width.
+    fn display_word(&self, word: &str) -> String {
+        width::truncate(word, self.max_word_width.unwrap_or(usize::MAX))
+    }
+
+    /// The width of the widest cell in each column.
+    fn widths(rows: &[Vec<(String, Align)>]) -> Vec<usize> {
+        let mut widths = Vec::new();
+        for row in rows {
+            widths.resize(widths.len().max(row.len()), 0);
+            for (width, (cell, _)) in widths.iter_mut().zip(row) {
+                *width = (*width).max(Self::width(cell));
+            }
+        }
+
+        widths
+    }
+
+    /// The width of a cell in terminal columns.
+    fn width(cell: &str) -> usize {
+        width::display_width(cell)
+    }
+}
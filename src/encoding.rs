@@ -0,0 +1,276 @@
+use crate::WordTallyError;
+use clap::ValueEnum;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{self, ErrorKind::InvalidData, Read};
+
+/// Text encodings that input can be transcoded from while it's read.
+///
+/// Input in any encoding other than `Utf8` is transcoded to UTF-8 before it's
+/// segmented into words, and bytes that aren't valid in the encoding are
+/// reported as a `WordTallyError::Utf8` with their byte offset. The legacy
+/// single-byte `Latin1` (ISO-8859-1) and `Windows1252` encodings map every
+/// byte to a char, so they never fail.
+///
+/// A byte order mark at the start of the input is skipped. For the Unicode
+/// encodings, it also overrides the encoding, so UTF-16 input with a byte
+/// order mark is read correctly even when `Utf8` is set.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
+pub enum Encoding {
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "utf-8"))]
+    #[value(name = "utf-8")]
+    Utf8,
+
+    #[cfg_attr(feature = "serde", serde(rename = "utf-16le"))]
+    #[value(name = "utf-16le")]
+    Utf16Le,
+
+    #[cfg_attr(feature = "serde", serde(rename = "utf-16be"))]
+    #[value(name = "utf-16be")]
+    Utf16Be,
+
+    #[cfg_attr(feature = "serde", serde(rename = "latin-1"))]
+    #[value(name = "latin-1")]
+    Latin1,
+
+    #[cfg_attr(feature = "serde", serde(rename = "windows-1252"))]
+    #[value(name = "windows-1252")]
+    Windows1252,
+}
+
+impl Encoding {
+    /// Wraps an input so it reads as UTF-8.
+    pub(crate) const fn decode<R: Read>(self, input: R) -> Decoder<R> {
+        Decoder {
+            input,
+            encoding: self,
+            raw: Vec::new(),
+            offset: 0,
+            decoded: String::new(),
+            position: 0,
+            bom_len: None,
+        }
+    }
+
+    /// Wraps an input so it reads as UTF-8, for reading text in this encoding
+    /// before it's tallied, like a field of each JSON Lines record.
+    ///
+    /// Text read this way is tallied with `Utf8`, so it isn't transcoded twice.
+    pub fn transcode<R: Read>(self, input: R) -> impl Read {
+        self.decode(input)
+    }
+
+    /// Decodes as much of `raw` as is complete into `decoded`, returning the number of bytes used.
+    fn decode_into(self, raw: &[u8], offset: usize, decoded: &mut String) -> io::Result<usize> {
+        match self {
+            Self::Utf8 => unreachable!("UTF-8 input is read without decoding"),
+            Self::Utf16Le => decode_utf16(self, raw, offset, decoded, u16::from_le_bytes),
+            Self::Utf16Be => decode_utf16(self, raw, offset, decoded, u16::from_be_bytes),
+            Self::Latin1 => {
+                decoded.extend(raw.iter().map(|&byte| char::from(byte)));
+                Ok(raw.len())
+            }
+            Self::Windows1252 => {
+                decoded.extend(raw.iter().map(|&byte| windows_1252(byte)));
+                Ok(raw.len())
+            }
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let encoding = match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+            Self::Latin1 => "latin-1",
+            Self::Windows1252 => "windows-1252",
+        };
+
+        f.write_str(encoding)
+    }
+}
+
+/// Byte order marks and the Unicode encodings they mark.
+const BOMS: [(&[u8], Encoding); 3] = [
+    (&[0xEF, 0xBB, 0xBF], Encoding::Utf8),
+    (&[0xFF, 0xFE], Encoding::Utf16Le),
+    (&[0xFE, 0xFF], Encoding::Utf16Be),
+];
+
+/// Windows-1252 chars for bytes `0x80` through `0x9F`, where it differs from Latin-1.
+///
+/// The five bytes it leaves undefined map to the C1 controls of the same value.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// Decodes a Windows-1252 byte, which is the same as Latin-1 outside `0x80` through `0x9F`.
+fn windows_1252(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+/// Decodes complete UTF-16 code units, leaving a trailing partial unit or lone high surrogate.
+fn decode_utf16(
+    encoding: Encoding,
+    raw: &[u8],
+    offset: usize,
+    decoded: &mut String,
+    unit: fn([u8; 2]) -> u16,
+) -> io::Result<usize> {
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .collect();
+    let mut used = 0;
+
+    for result in char::decode_utf16(units.iter().copied()) {
+        match result {
+            Ok(ch) => {
+                decoded.push(ch);
+                used += ch.len_utf16() * 2;
+            }
+            Err(err)
+                if used + 2 == units.len() * 2
+                    && (0xD800..0xDC00).contains(&err.unpaired_surrogate()) =>
+            {
+                break;
+            }
+            Err(_) => return Err(invalid(encoding, offset + used, &raw[used..])),
+        }
+    }
+
+    Ok(used)
+}
+
+/// Constructs an invalid data error for the bytes from `offset` onward that
+/// aren't valid in `encoding`, to be unwrapped into a `WordTallyError::Utf8`.
+fn invalid(encoding: Encoding, offset: usize, bytes: &[u8]) -> io::Error {
+    io::Error::new(
+        InvalidData,
+        WordTallyError::invalid(encoding, offset, bytes),
+    )
+}
+
+/// A reader that transcodes input in an `Encoding` to UTF-8.
+pub(crate) struct Decoder<R> {
+    /// Input in the encoding.
+    input: R,
+
+    /// Encoding of the input.
+    encoding: Encoding,
+
+    /// Input bytes read but not yet decoded, like half of a code unit.
+    raw: Vec<u8>,
+
+    /// Byte offset in the input of the start of `raw`.
+    offset: usize,
+
+    /// Text decoded from the input but not yet read.
+    decoded: String,
+
+    /// Position of the next unread byte of `decoded`.
+    position: usize,
+
+    /// Length of the byte order mark skipped at the start of the input, once it's been checked.
+    bom_len: Option<usize>,
+}
+
+impl<R: Read> Decoder<R> {
+    /// Gets the length of the byte order mark skipped at the start of the input.
+    pub(crate) fn bom_len(&self) -> usize {
+        self.bom_len.unwrap_or_default()
+    }
+
+    /// Skips a byte order mark at the start of the input, switching to the
+    /// Unicode encoding it marks unless the input is in a single-byte encoding.
+    fn skip_bom(&mut self) -> io::Result<()> {
+        let mut head = [0; 3];
+        let mut len = 0;
+        while len < head.len() {
+            match self.input.read(&mut head[len..])? {
+                0 => break,
+                read => len += read,
+            }
+        }
+        let head = &head[..len];
+
+        let bom = match self.encoding {
+            Encoding::Utf8 | Encoding::Utf16Le | Encoding::Utf16Be => {
+                BOMS.into_iter().find(|(bom, _)| head.starts_with(bom))
+            }
+            Encoding::Latin1 | Encoding::Windows1252 => None,
+        };
+        let bom_len = bom.map_or(0, |(bom, encoding)| {
+            self.encoding = encoding;
+            bom.len()
+        });
+        self.raw.extend_from_slice(&head[bom_len..]);
+        self.offset = bom_len;
+        self.bom_len = Some(bom_len);
+
+        Ok(())
+    }
+
+    /// Reads and decodes input until there's text to read, returning `false` at the end of input.
+    fn fill(&mut self) -> io::Result<bool> {
+        let mut chunk = [0; 8 * 1024];
+        self.decoded.clear();
+        self.position = 0;
+
+        loop {
+            let used = self
+                .encoding
+                .decode_into(&self.raw, self.offset, &mut self.decoded)?;
+            self.raw.drain(..used);
+            self.offset += used;
+            if !self.decoded.is_empty() {
+                return Ok(true);
+            }
+
+            let len = self.input.read(&mut chunk)?;
+            if len == 0 {
+                if self.raw.is_empty() {
+                    return Ok(false);
+                }
+                return Err(invalid(self.encoding, self.offset, &self.raw));
+            }
+            self.raw.extend_from_slice(&chunk[..len]);
+        }
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.bom_len.is_none() {
+            self.skip_bom()?;
+        }
+        if self.encoding == Encoding::Utf8 {
+            if self.raw.is_empty() {
+                return self.input.read(buf);
+            }
+            let len = buf.len().min(self.raw.len());
+            buf[..len].copy_from_slice(&self.raw[..len]);
+            self.raw.drain(..len);
+            return Ok(len);
+        }
+        if self.position == self.decoded.len() && !self.fill()? {
+            return Ok(0);
+        }
+
+        let unread = &self.decoded.as_bytes()[self.position..];
+        let len = buf.len().min(unread.len());
+        buf[..len].copy_from_slice(&unread[..len]);
+        self.position += len;
+
+        Ok(len)
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{Encoding, Source};
+use core::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::Utf8Error;
+
+/// Errors that can occur while tallying words from an input.
+///
+/// Each error carries the path of the source it happened in, when it's known.
+/// A source that fails to open or read sets it, and `locate` sets it for
+/// errors at an offset into a `Source::List`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WordTallyError {
+    /// Reading from the input failed.
+    Io {
+        /// Path of the source that failed, if known.
+        source_path: Option<PathBuf>,
+
+        /// The error reading it.
+        error: io::Error,
+    },
+
+    /// The input contains bytes that aren't valid UTF-8, or in its `Encoding`.
+    Utf8 {
+        /// Path of the source with the invalid bytes, if known.
+        source_path: Option<PathBuf>,
+
+        /// Encoding the input was read in.
+        encoding: Encoding,
+
+        /// Byte offset of the first invalid byte, within `source_path` if it's set
+        /// or the whole input otherwise.
+        offset: usize,
+
+        /// The invalid bytes, and up to a few after them, for locating the bad data.
+        bytes: Box<[u8]>,
+    },
+
+    /// More input would be held in memory at once than `Options::max_buffer` allows.
+    TooLarge {
+        /// Path of the source that went over the limit, if known.
+        source_path: Option<PathBuf>,
+
+        /// Byte offset of the line that went over the limit, within
+        /// `source_path` if it's set or the whole input otherwise.
+        offset: usize,
+
+        /// Most bytes allowed to be buffered.
+        limit: usize,
+    },
+}
+
+/// The kind of a `WordTallyError`, for handling errors without matching their fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Reading failed, or a word list or source list was invalid.
+    Io,
+
+    /// The input contains bytes that aren't valid UTF-8, or in its `Encoding`.
+    Utf8,
+
+    /// A line, paragraph, document or source list was too large to buffer.
+    TooLarge,
+}
+
+impl WordTallyError {
+    /// Most bytes kept from the offending position onward.
+    const SNIPPET_LEN: usize = 8;
+
+    /// Gets the `ErrorKind` of the error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Io { .. } => ErrorKind::Io,
+            Self::Utf8 { .. } => ErrorKind::Utf8,
+            Self::TooLarge { .. } => ErrorKind::TooLarge,
+        }
+    }
+
+    /// Returns whether reading failed or a word list was invalid.
+    pub const fn is_io(&self) -> bool {
+        matches!(self.kind(), ErrorKind::Io)
+    }
+
+    /// Returns whether the input wasn't valid UTF-8, or in its `Encoding`.
+    pub const fn is_utf8(&self) -> bool {
+        matches!(self.kind(), ErrorKind::Utf8)
+    }
+
+    /// Returns whether more input would be buffered than allowed.
+    pub const fn is_too_large(&self) -> bool {
+        matches!(self.kind(), ErrorKind::TooLarge)
+    }
+
+    /// Gets the path of the source the error happened in, if it's known.
+    pub fn source_path(&self) -> Option<&Path> {
+        match self {
+            Self::Io { source_path, .. }
+            | Self::Utf8 { source_path, .. }
+            | Self::TooLarge { source_path, .. } => source_path.as_deref(),
+        }
+    }
+
+    /// Sets the path of the file an error at an offset into the input from
+    /// `source` happened in, and makes the offset relative to that file.
+    ///
+    /// Errors from a `List` are found by the sizes of the files before them,
+    /// so the input must have been read with `Source::open`. Errors that
+    /// already have a path, or aren't in a file, are returned as is.
+    #[must_use]
+    pub fn locate(self, source: &Source) -> Self {
+        if self.source_path().is_some() {
+            return self;
+        }
+        let locate = |offset: usize| match source.locate(offset as u64) {
+            Some((Source::Path(path), offset)) => Some((path.clone(), offset as usize)),
+            _ => None,
+        };
+
+        match self {
+            Self::Utf8 {
+                encoding,
+                offset,
+                bytes,
+                ..
+            } => match locate(offset) {
+                Some((path, offset)) => Self::Utf8 {
+                    source_path: Some(path),
+                    encoding,
+                    offset,
+                    bytes,
+                },
+                None => Self::invalid_at(encoding, offset, bytes),
+            },
+            Self::TooLarge { offset, limit, .. } => match locate(offset) {
+                Some((path, offset)) => Self::TooLarge {
+                    source_path: Some(path),
+                    offset,
+                    limit,
+                },
+                None => Self::too_large(offset, limit),
+            },
+            err => err,
+        }
+    }
+
+    /// Constructs a `Utf8` error for a line starting at `line_offset` in the input.
+    pub(crate) fn utf8(line_offset: usize, line: &[u8], err: Utf8Error) -> Self {
+        let invalid = &line[err.valid_up_to()..];
+        let len = invalid.len().min(Self::SNIPPET_LEN);
+
+        Self::invalid_at(
+            Encoding::Utf8,
+            line_offset + err.valid_up_to(),
+            invalid[..len].into(),
+        )
+    }
+
+    /// Constructs a `Utf8` error for input that isn't valid in `encoding`,
+    /// with the bytes from `offset` in the input onward.
+    pub(crate) fn invalid(encoding: Encoding, offset: usize, bytes: &[u8]) -> Self {
+        let len = bytes.len().min(Self::SNIPPET_LEN);
+
+        Self::invalid_at(encoding, offset, bytes[..len].into())
+    }
+
+    /// Constructs a `Utf8` error at an offset in the input.
+    const fn invalid_at(encoding: Encoding, offset: usize, bytes: Box<[u8]>) -> Self {
+        Self::Utf8 {
+            source_path: None,
+            encoding,
+            offset,
+            bytes,
+        }
+    }
+
+    /// Constructs a `TooLarge` error at an offset in the input.
+    pub(crate) const fn too_large(offset: usize, limit: usize) -> Self {
+        Self::TooLarge {
+            source_path: None,
+            offset,
+            limit,
+        }
+    }
+
+    /// Shifts the offset of a `Utf8` or `TooLarge` error for input read from `base` onward.
+    pub(crate) fn offset_by(self, base: usize) -> Self {
+        match self {
+            Self::Utf8 {
+                source_path,
+                encoding,
+                offset,
+                bytes,
+            } => Self::Utf8 {
+                source_path,
+                encoding,
+                offset: base + offset,
+                bytes,
+            },
+            Self::TooLarge {
+                source_path,
+                offset,
+                limit,
+            } => Self::TooLarge {
+                source_path,
+                offset: base + offset,
+                limit,
+            },
+            err => err,
+        }
+    }
+}
+
+impl Display for WordTallyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let of = self
+            .source_path()
+            .map(|path| format!(" of {}", path.display()))
+            .unwrap_or_default();
+
+        match self {
+            Self::Io {
+                source_path: Some(path),
+                ..
+            } => write!(f, "Failed to read {}", path.display()),
+            Self::Io { .. } => f.write_str("Failed to read input"),
+            Self::Utf8 {
+                encoding,
+                offset,
+                bytes,
+                ..
+            } => {
+                let encoding = encoding.to_string().to_uppercase();
+                let hex: Vec<String> = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+                write!(
+                    f,
+                    "Invalid {encoding} at byte {offset}{of}: {}",
+                    hex.join(" ")
+                )
+            }
+            Self::TooLarge { offset, limit, .. } => {
+                write!(
+                    f,
+                    "Input at byte {offset}{of} exceeds the {limit} byte buffer limit"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordTallyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Utf8 { .. } | Self::TooLarge { .. } => None,
+        }
+    }
+}
+
+/// An `io::Error` reading a source at a path, unwrapped into `WordTallyError::Io`.
+#[derive(Debug)]
+pub(crate) struct PathError {
+    path: PathBuf,
+    error: io::Error,
+}
+
+impl PathError {
+    /// Wraps an error reading from a path, unless it's already wrapped.
+    pub(crate) fn wrap(path: &Path, error: io::Error) -> io::Error {
+        if error.get_ref().is_some_and(|inner| inner.is::<Self>()) {
+            return error;
+        }
+
+        let path = path.to_path_buf();
+        io::Error::new(error.kind(), Self { path, error })
+    }
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+impl From<io::Error> for WordTallyError {
+    /// Converts an `io::Error`, unwrapping a `PathError` or a `WordTallyError`
+    /// returned through a reader, like a `Utf8` error decoding the input.
+    fn from(error: io::Error) -> Self {
+        let wrapped = error
+            .get_ref()
+            .is_some_and(|inner| inner.is::<PathError>() || inner.is::<Self>());
+        if !wrapped {
+            return Self::Io {
+                source_path: None,
+                error,
+            };
+        }
+
+        let Some(inner) = error.into_inner() else {
+            unreachable!("The error was checked to wrap an error.")
+        };
+        match inner.downcast::<Self>() {
+            Ok(err) => *err,
+            Err(inner) => {
+                let Ok(path_error) = inner.downcast::<PathError>() else {
+                    unreachable!("The error was checked to wrap a `PathError`.")
+                };
+                Self::Io {
+                    source_path: Some(path_error.path),
+                    error: path_error.error,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use clap::ValueEnum;
+use core::fmt::{self, Display, Formatter};
+use std::error::Error;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+use word_tally::WordTallyError;
+
+/// Schemes for mapping errors to process exit codes.
+///
+/// `Sysexits` follows BSD `sysexits.h`, distinguishing bad data, missing
+/// input, unwritable output, permissions and I/O failures. `Simple` exits with
+/// 1 for any error. Invalid arguments exit with 2 in either scheme.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum ExitCodes {
+    #[default]
+    Sysexits,
+    Simple,
+}
+
+/// `EX_DATAERR`: the input or a word list wasn't valid, like invalid UTF-8 or JSON, or was too large to buffer.
+const DATA_ERR: u8 = 65;
+
+/// `EX_NOINPUT`: the input didn't exist or had nothing to tally.
+const NO_INPUT: u8 = 66;
+
+/// `EX_CANTCREAT`: the output file couldn't be created.
+const CANT_CREAT: u8 = 73;
+
+/// `EX_IOERR`: reading or writing failed.
+const IO_ERR: u8 = 74;
+
+/// `EX_NOPERM`: a file couldn't be accessed.
+const NO_PERM: u8 = 77;
+
+/// Exit code for any other error.
+const FAILURE: u8 = 1;
+
+impl ExitCodes {
+    /// Gets the process exit code for an error.
+    pub fn code(self, err: &anyhow::Error) -> u8 {
+        match self {
+            Self::Sysexits => err.chain().find_map(Self::sysexit).unwrap_or(FAILURE),
+            Self::Simple => FAILURE,
+        }
+    }
+
+    /// Gets the `sysexits.h` code for an error in a chain, if it has one.
+    fn sysexit(err: &(dyn Error + 'static)) -> Option<u8> {
+        if err.is::<EmptyInput>() {
+            return Some(NO_INPUT);
+        }
+
+        // Checked before the `io::Error` after it, which could be `NotFound` for a missing directory.
+        if err.is::<CantCreate>() {
+            return Some(CANT_CREAT);
+        }
+
+        // A `WordTallyError::Io` is mapped by the `io::Error` after it in the chain.
+        if let Some(WordTallyError::Utf8 { .. } | WordTallyError::TooLarge { .. }) =
+            err.downcast_ref::<WordTallyError>()
+        {
+            return Some(DATA_ERR);
+        }
+
+        err.downcast_ref::<io::Error>().map(|err| match err.kind() {
+            ErrorKind::NotFound => NO_INPUT,
+            ErrorKind::PermissionDenied => NO_PERM,
+            ErrorKind::InvalidData => DATA_ERR,
+            _ => IO_ERR,
+        })
+    }
+}
+
+/// No words were tallied from a source when empty input is treated as an error.
+#[derive(Debug)]
+pub struct EmptyInput {
+    pub source: String,
+}
+
+impl Display for EmptyInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "No words tallied from {}", self.source)
+    }
+}
+
+impl Error for EmptyInput {}
+
+/// The output file couldn't be created.
+#[derive(Debug)]
+pub struct CantCreate {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl Display for CantCreate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to create file: {:?}", self.path)
+    }
+}
+
+impl Error for CantCreate {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::Case;
+use crate::{syllables, Case, WordType};
+use clap::ValueEnum;
 use core::fmt::{self, Display, Formatter};
 use indexmap::IndexMap;
 use std::collections::HashSet;
@@ -10,11 +11,23 @@ pub struct Filters {
     /// Minimum characters required for a word.
     pub min_chars: Option<MinChars>,
 
+    /// Unit used to measure the length of a word for `min_chars`.
+    pub length_unit: LengthUnit,
+
+    /// Minimum estimated syllables required for a word.
+    pub min_syllables: Option<MinSyllables>,
+
     /// Minimum count for number of times a word must appear.
     pub min_count: Option<MinCount>,
 
     /// List of specific words to exclude.
     pub exclude: Option<ExcludeWords>,
+
+    /// Heuristics for dropping tokens that are likely noise rather than words.
+    pub denoise: Option<Denoise>,
+
+    /// Kinds of words to tally, excluding all others.
+    pub word_types: Option<WordTypes>,
 }
 
 impl Filters {
@@ -28,6 +41,41 @@ impl Filters {
             min_chars: min_chars.map(MinChars),
             min_count: min_count.map(MinCount),
             exclude: exclude.map(ExcludeWords),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the unit used to measure word length.
+    pub fn with_length_unit(self, length_unit: LengthUnit) -> Self {
+        Self {
+            length_unit,
+            ..self
+        }
+    }
+
+    /// Sets the minimum estimated syllables required for a word.
+    pub fn with_min_syllables(self, min_syllables: Option<usize>) -> Self {
+        Self {
+            min_syllables: min_syllables.map(MinSyllables),
+            ..self
+        }
+    }
+
+    /// Sets the heuristics for dropping noisy tokens.
+    pub fn with_denoise(self, denoise: Option<Denoise>) -> Self {
+        Self { denoise, ..self }
+    }
+
+    /// Sets the list of words to exclude.
+    pub fn with_exclude(self, exclude: Option<ExcludeWords>) -> Self {
+        Self { exclude, ..self }
+    }
+
+    /// Sets the kinds of words to tally.
+    pub fn with_word_types(self, word_types: Option<Vec<WordType>>) -> Self {
+        Self {
+            word_types: word_types.map(WordTypes),
+            ..self
         }
     }
 
@@ -37,13 +85,31 @@ impl Filters {
             tally_map.retain(|_, &mut count| count >= min_count);
         }
 
-        if let Some(MinChars(min_chars)) = self.min_chars {
-            tally_map.retain(|word, _| word.graphemes(true).count() >= min_chars);
-        }
+        let keep = self.word_filter(case);
+        tally_map.retain(|word, _| keep(word));
+    }
+
+    /// Builds a check for whether a word passes every filter that doesn't need its count.
+    pub(crate) fn word_filter(&self, case: Case) -> impl Fn(&str) -> bool + '_ {
+        let discard: Option<HashSet<_>> = self
+            .exclude
+            .as_ref()
+            .map(|ExcludeWords(words)| words.iter().map(|word| case.normalize(word)).collect());
 
-        if let Some(ExcludeWords(words)) = &self.exclude {
-            let discard: HashSet<_> = words.iter().map(|word| case.normalize(word)).collect();
-            tally_map.retain(|word, _| !discard.contains(word));
+        move |word| {
+            self.min_chars
+                .is_none_or(|MinChars(min_chars)| self.length_unit.len(word) >= min_chars)
+                && self
+                    .min_syllables
+                    .is_none_or(|MinSyllables(min_syllables)| syllables(word) >= min_syllables)
+                && discard
+                    .as_ref()
+                    .is_none_or(|discard| !discard.contains(word))
+                && self.denoise.is_none_or(|denoise| !denoise.is_noise(word))
+                && self
+                    .word_types
+                    .as_ref()
+                    .is_none_or(|WordTypes(word_types)| word_types.contains(&WordType::of(word)))
         }
     }
 }
@@ -64,6 +130,122 @@ impl From<usize> for MinChars {
     }
 }
 
+/// Minimum number of estimated syllables a word needs to have to be tallied.
+///
+/// Syllables are estimated with `syllables`, so words without letters have none.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct MinSyllables(pub usize);
+
+impl Display for MinSyllables {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<usize> for MinSyllables {
+    fn from(raw: usize) -> Self {
+        Self(raw)
+    }
+}
+
+/// Unit for measuring the length of a word.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum LengthUnit {
+    Bytes,
+    Chars,
+    #[default]
+    Graphemes,
+}
+
+impl LengthUnit {
+    /// Measures the length of a word in this unit.
+    pub fn len(&self, word: &str) -> usize {
+        match self {
+            Self::Bytes => word.len(),
+            Self::Chars => word.chars().count(),
+            Self::Graphemes => word.graphemes(true).count(),
+        }
+    }
+}
+
+impl Display for LengthUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let unit = match self {
+            Self::Bytes => "bytes",
+            Self::Chars => "chars",
+            Self::Graphemes => "graphemes",
+        };
+
+        f.write_str(unit)
+    }
+}
+
+/// Heuristics for recognizing tokens that are likely noise rather than words.
+///
+/// `Ocr` recognizes misreads from scanned text, like letters mixed with
+/// digits or repeated punctuation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum Denoise {
+    Ocr,
+}
+
+impl Denoise {
+    /// Whether a word looks like noise.
+    pub fn is_noise(&self, word: &str) -> bool {
+        match self {
+            Self::Ocr => Self::has_repeated_punctuation(word) || Self::is_mixed_misread(word),
+        }
+    }
+
+    /// Whether a word has adjacent punctuation, like `it''s` or `a..b`.
+    ///
+    /// Combining marks aren't punctuation, so decomposed words like `việt` aren't noise.
+    fn has_repeated_punctuation(word: &str) -> bool {
+        word.chars()
+            .zip(word.chars().skip(1))
+            .any(|(a, b)| Self::is_punctuation(a) && Self::is_punctuation(b))
+    }
+
+    /// Whether a char is ASCII punctuation or in the General Punctuation block, like curly quotes.
+    const fn is_punctuation(ch: char) -> bool {
+        ch.is_ascii_punctuation() || matches!(ch, '\u{2010}'..='\u{205E}')
+    }
+
+    /// Whether a Latin word mixes letters and digits with few vowels, like `tb1rn`.
+    ///
+    /// Ordinals like `2nd` are digits followed by a suffix, so they aren't noise.
+    fn is_mixed_misread(word: &str) -> bool {
+        const ORDINALS: [&str; 4] = ["st", "nd", "rd", "th"];
+
+        let letters: Vec<char> = word.chars().filter(|ch| ch.is_alphabetic()).collect();
+        let has_digit = word.chars().any(|ch| ch.is_ascii_digit());
+        if letters.is_empty() || !has_digit || !letters.iter().all(char::is_ascii) {
+            return false;
+        }
+
+        let digits = word.trim_end_matches(char::is_alphabetic);
+        let suffix = &word[digits.len()..];
+        let is_ordinal = digits.chars().all(|ch| ch.is_ascii_digit())
+            && ORDINALS
+                .iter()
+                .any(|ordinal| suffix.eq_ignore_ascii_case(ordinal));
+        let vowels = letters
+            .iter()
+            .filter(|ch| matches!(ch.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u' | 'y'))
+            .count();
+
+        !is_ordinal && vowels * 3 < letters.len()
+    }
+}
+
+impl Display for Denoise {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ocr => f.write_str("ocr"),
+        }
+    }
+}
+
 /// Minimum number of times a word needs to appear to be tallied.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct MinCount(pub usize);
@@ -84,6 +266,28 @@ impl From<usize> for MinCount {
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct ExcludeWords(pub Vec<String>);
 
+impl ExcludeWords {
+    /// Parses `ExcludeWords` from raw entries, trimming each one and dropping
+    /// empty entries and duplicates once normalized by `case`.
+    ///
+    /// Returns the words along with the number of entries dropped.
+    pub fn parse<S: AsRef<str>>(entries: impl IntoIterator<Item = S>, case: Case) -> (Self, usize) {
+        let mut seen = HashSet::new();
+        let mut words = Vec::new();
+        let mut dropped = 0;
+        for entry in entries {
+            let word = entry.as_ref().trim();
+            if word.is_empty() || !seen.insert(case.normalize(word)) {
+                dropped += 1;
+            } else {
+                words.push(word.to_string());
+            }
+        }
+
+        (Self(words), dropped)
+    }
+}
+
 impl Display for ExcludeWords {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0.join(","))
@@ -95,3 +299,20 @@ impl From<Vec<String>> for ExcludeWords {
         Self(raw)
     }
 }
+
+/// A list of the kinds of words that should be tallied.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct WordTypes(pub Vec<WordType>);
+
+impl Display for WordTypes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let word_types: Vec<_> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", word_types.join(","))
+    }
+}
+
+impl From<Vec<WordType>> for WordTypes {
+    fn from(raw: Vec<WordType>) -> Self {
+        Self(raw)
+    }
+}
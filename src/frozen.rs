@@ -0,0 +1,64 @@
+use crate::WordTally;
+
+/// A read-only tally sorted by word for repeated lookups.
+///
+/// A `FrozenTally` is built once from a `WordTally` and then queried by word
+/// or prefix with a binary search, without hashing or further allocation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FrozenTally {
+    /// Ordered pairs of words and the count of times they appear, sorted by word.
+    tally: Box<[(Box<str>, usize)]>,
+}
+
+/// A `FrozenTally` is constructed by sorting a `WordTally` by word.
+impl From<WordTally> for FrozenTally {
+    fn from(word_tally: WordTally) -> Self {
+        let mut tally = word_tally.into_tally();
+        tally.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        Self { tally }
+    }
+}
+
+impl FrozenTally {
+    /// Gets the count for a word, if it was tallied.
+    pub fn get(&self, word: &str) -> Option<usize> {
+        self.position(word).ok().map(|index| self.tally[index].1)
+    }
+
+    /// Returns whether a word was tallied.
+    pub fn contains(&self, word: &str) -> bool {
+        self.position(word).is_ok()
+    }
+
+    /// Gets the contiguous run of entries whose words begin with `prefix`.
+    pub fn prefixed(&self, prefix: &str) -> &[(Box<str>, usize)] {
+        let start = self
+            .tally
+            .partition_point(|(word, _)| word.as_ref() < prefix);
+        let len = self.tally[start..].partition_point(|(word, _)| word.starts_with(prefix));
+
+        &self.tally[start..start + len]
+    }
+
+    /// Gets the `tally` field.
+    pub const fn tally(&self) -> &[(Box<str>, usize)] {
+        &self.tally
+    }
+
+    /// Gets the number of unique words.
+    pub const fn len(&self) -> usize {
+        self.tally.len()
+    }
+
+    /// Returns whether no words were tallied.
+    pub const fn is_empty(&self) -> bool {
+        self.tally.is_empty()
+    }
+
+    /// Binary searches for the index of a word.
+    fn position(&self, word: &str) -> Result<usize, usize> {
+        self.tally
+            .binary_search_by(|(candidate, _)| candidate.as_ref().cmp(word))
+    }
+}
@@ -0,0 +1,144 @@
+use core::cmp::Reverse;
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+
+/// Chars per edit a word needs, so short words like `cat` and `hat` aren't merged.
+const CHARS_PER_EDIT: usize = 4;
+
+/// Longest word considered for merging, since a word has `O(len^distance)` deletion variants.
+const MAX_WORD_LEN: usize = 32;
+
+/// Merges words within `max_distance` edits of a more frequent word into it.
+///
+/// Words are visited from most to least frequent so the most common spelling
+/// becomes the canonical entry. Candidates are found with a deletion index
+/// (any two words within `n` edits share a variant with at most `n` chars
+/// deleted from each) and confirmed with a Levenshtein distance check.
+/// Canonical entries keep the position of their first appearance.
+///
+/// Two words are only merged within one edit per `CHARS_PER_EDIT` chars of
+/// the shorter word, and words longer than `MAX_WORD_LEN` chars are left as is.
+pub fn merge(tally_map: &mut IndexMap<Box<str>, usize>, max_distance: usize) {
+    if max_distance == 0 {
+        return;
+    }
+
+    let mut counts: Vec<usize> = tally_map.values().copied().collect();
+    let mut by_count: Vec<usize> = (0..counts.len()).collect();
+    by_count.sort_by_key(|&position| Reverse(counts[position]));
+
+    let chars: Vec<Vec<char>> = tally_map
+        .keys()
+        .map(|word| word.chars().collect())
+        .collect();
+    let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut merged_into = vec![None; counts.len()];
+
+    for position in by_count {
+        let word = &chars[position];
+        let distance = allowed_distance(word, max_distance);
+        if distance == 0 {
+            continue;
+        }
+        let variants = deletions(word, distance);
+        let canonical = nearest(word, &variants, &index, &chars, &counts, distance);
+
+        match canonical {
+            Some(canonical) => {
+                counts[canonical] += counts[position];
+                merged_into[position] = Some(canonical);
+            }
+            None => {
+                for variant in variants {
+                    index.entry(variant).or_default().push(position);
+                }
+            }
+        }
+    }
+
+    let mut merged = merged_into.into_iter().zip(counts);
+    tally_map.retain(|_, count| {
+        let (canonical, total) = merged.next().expect("Each entry has a merge result.");
+        *count = total;
+        canonical.is_none()
+    });
+}
+
+/// Finds the closest indexed word, preferring the highest count on ties.
+///
+/// A candidate must be within `max_distance` edits, and within its own allowed distance.
+fn nearest(
+    word: &[char],
+    variants: &HashSet<String>,
+    index: &HashMap<String, Vec<usize>>,
+    chars: &[Vec<char>],
+    counts: &[usize],
+    max_distance: usize,
+) -> Option<usize> {
+    variants
+        .iter()
+        .filter_map(|variant| index.get(variant))
+        .flatten()
+        .copied()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|candidate| (levenshtein(word, &chars[candidate]), candidate))
+        .filter(|&(distance, candidate)| {
+            distance <= allowed_distance(&chars[candidate], max_distance)
+        })
+        .min_by_key(|&(distance, candidate)| (distance, Reverse(counts[candidate]), candidate))
+        .map(|(_, candidate)| candidate)
+}
+
+/// The most edits a word can be merged within, or 0 if it's too short or long to merge.
+const fn allowed_distance(word: &[char], max_distance: usize) -> usize {
+    if word.len() > MAX_WORD_LEN {
+        return 0;
+    }
+
+    let distance = word.len() / CHARS_PER_EDIT;
+    if distance < max_distance {
+        distance
+    } else {
+        max_distance
+    }
+}
+
+/// Every variant of `word` with up to `max_distance` chars deleted, including itself.
+fn deletions(word: &[char], max_distance: usize) -> HashSet<String> {
+    let mut variants = HashSet::from([word.iter().collect::<String>()]);
+    let mut frontier = vec![word.to_vec()];
+
+    for _ in 0..max_distance {
+        let mut next = Vec::new();
+        for chars in &frontier {
+            for skip in 0..chars.len() {
+                let mut variant = chars.clone();
+                variant.remove(skip);
+                if variants.insert(variant.iter().collect()) {
+                    next.push(variant);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    variants
+}
+
+/// The number of single char insertions, deletions or substitutions between two words.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
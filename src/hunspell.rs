@@ -0,0 +1,319 @@
+use crate::WordTallyError;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, ErrorKind::InvalidData};
+use std::path::{Path, PathBuf};
+
+/// Hunspell flags are parsed into a number regardless of their `FLAG` format.
+type Flag = u32;
+
+/// A Hunspell dictionary for folding inflected words to their stems.
+///
+/// Stems and their flags are read from a `.dic` file and the prefix and
+/// suffix rules that inflect them from an `.aff` file. Compounding and other
+/// morphological analysis aren't supported; a word folds to the first stem
+/// found by undoing a single prefix, a single suffix, or a cross product of both.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dictionary {
+    /// Stems with the flags of the affixes they accept.
+    stems: HashMap<Box<str>, Box<[Flag]>>,
+
+    /// Prefix rules keyed by the text they add.
+    prefixes: HashMap<Box<str>, Vec<Affix>>,
+
+    /// Suffix rules keyed by the text they add.
+    suffixes: HashMap<Box<str>, Vec<Affix>>,
+}
+
+impl Dictionary {
+    /// Reads a dictionary from `.aff` and `.dic` files sharing a path stem, like `en_US`.
+    ///
+    /// The extensions are appended to the path, so a stem with a dot like `en.US` is kept whole.
+    pub fn open(path: &Path) -> Result<Self, WordTallyError> {
+        let with_extension = |extension: &str| {
+            let mut path = path.as_os_str().to_owned();
+            path.push(extension);
+            PathBuf::from(path)
+        };
+        let aff = fs::read_to_string(with_extension(".aff"))?;
+        let dic = fs::read_to_string(with_extension(".dic"))?;
+
+        Self::parse(&aff, &dic)
+    }
+
+    /// Parses a dictionary from the contents of its `.aff` and `.dic` files.
+    pub fn parse(aff: &str, dic: &str) -> Result<Self, WordTallyError> {
+        let mut dictionary = Self::default();
+        let format = FlagFormat::from_aff(aff);
+        let mut cross_products = HashMap::new();
+
+        for line in aff.lines().map(str::trim) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (kind, flag, rest) = match fields.as_slice() {
+                [kind @ ("PFX" | "SFX"), flag, rest @ ..] => (*kind, *flag, rest),
+                _ => continue,
+            };
+            let flag = format.parse_one(flag)?;
+
+            match rest {
+                [cross, count] if count.parse::<usize>().is_ok() => {
+                    cross_products.insert((kind, flag), *cross == "Y");
+                }
+                [strip, add, condition @ ..] => {
+                    let add = add.split('/').next().unwrap_or_default();
+                    let affix = Affix {
+                        flag,
+                        cross_product: cross_products.get(&(kind, flag)) == Some(&true),
+                        strip: Self::field(strip),
+                        condition: Condition::parse(condition.first().copied().unwrap_or("."))?,
+                    };
+                    let rules = if kind == "PFX" {
+                        &mut dictionary.prefixes
+                    } else {
+                        &mut dictionary.suffixes
+                    };
+                    rules.entry(Self::field(add)).or_default().push(affix);
+                }
+                _ => {
+                    return Err(io::Error::new(InvalidData, format!("Invalid rule: {line}")).into())
+                }
+            }
+        }
+
+        for (index, line) in dic.lines().enumerate() {
+            let entry = line.split_whitespace().next().unwrap_or_default();
+            if entry.is_empty() || (index == 0 && entry.parse::<usize>().is_ok()) {
+                continue;
+            }
+
+            let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+            dictionary
+                .stems
+                .insert(word.into(), format.parse_all(flags)?.into_boxed_slice());
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Finds the stem of a word, trying its lowercase form if it isn't found as is.
+    pub fn stem(&self, word: &str) -> Option<&str> {
+        self.stem_exact(word)
+            .or_else(|| self.stem_exact(&word.to_lowercase()))
+    }
+
+    /// Finds the stem of a word by undoing at most one prefix and one suffix.
+    fn stem_exact(&self, word: &str) -> Option<&str> {
+        if let Some((stem, _)) = self.stems.get_key_value(word) {
+            return Some(stem);
+        }
+
+        if let Some(stem) = self.unsuffix(word, |_| true) {
+            return Some(stem);
+        }
+
+        let heads = Self::boundaries(word).map(|index| &word[..index]);
+        for (base, prefix) in Self::unaffix(&self.prefixes, word, heads, Affix::unprefix) {
+            if let Some((stem, _)) = self.stems.get_key_value(base.as_str()) {
+                if self.has_flag(stem, prefix.flag) {
+                    return Some(stem);
+                }
+            }
+
+            if prefix.cross_product {
+                let stem = self.unsuffix(&base, |(stem, suffix)| {
+                    suffix.cross_product && self.has_flag(stem, prefix.flag)
+                });
+                if stem.is_some() {
+                    return stem;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Finds a stem by undoing a suffix rule, if the stem and rule satisfy `accept`.
+    fn unsuffix(&self, word: &str, accept: impl Fn((&str, &Affix)) -> bool) -> Option<&str> {
+        let tails = Self::boundaries(word).map(|index| &word[index..]);
+        Self::unaffix(&self.suffixes, word, tails, Affix::unsuffix).find_map(|(base, suffix)| {
+            let (stem, _) = self.stems.get_key_value(base.as_str())?;
+            (self.has_flag(stem, suffix.flag) && accept((stem, suffix))).then_some(stem.as_ref())
+        })
+    }
+
+    /// Candidate stems for a word from the rules that add each of `adds`.
+    fn unaffix<'a>(
+        rules: &'a HashMap<Box<str>, Vec<Affix>>,
+        word: &'a str,
+        adds: impl Iterator<Item = &'a str> + 'a,
+        undo: fn(&Affix, &str, &str) -> Option<String>,
+    ) -> impl Iterator<Item = (String, &'a Affix)> + 'a {
+        adds.filter_map(move |add| rules.get(add).map(|affixes| (add, affixes)))
+            .flat_map(move |(add, affixes)| {
+                affixes
+                    .iter()
+                    .filter_map(move |affix| undo(affix, word, add).map(|base| (base, affix)))
+            })
+    }
+
+    /// Every char boundary in a word, including its start and end.
+    fn boundaries(word: &str) -> impl Iterator<Item = usize> + '_ {
+        word.char_indices()
+            .map(|(index, _)| index)
+            .chain([word.len()])
+    }
+
+    /// Whether a stem accepts the affix with the given flag.
+    fn has_flag(&self, stem: &str, flag: Flag) -> bool {
+        self.stems
+            .get(stem)
+            .is_some_and(|flags| flags.contains(&flag))
+    }
+
+    /// Reads an affix field, where `"0"` stands for empty text.
+    fn field(raw: &str) -> Box<str> {
+        if raw == "0" { "" } else { raw }.into()
+    }
+}
+
+/// A prefix or suffix rule from an `.aff` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Affix {
+    /// The flag stems carry to accept this affix.
+    flag: Flag,
+
+    /// Whether the affix combines with affixes of the other kind.
+    cross_product: bool,
+
+    /// Text removed from the stem before adding the affix.
+    strip: Box<str>,
+
+    /// Pattern the stem must match for the affix to apply.
+    condition: Condition,
+}
+
+impl Affix {
+    /// Undoes a prefix rule, returning the stem it would have applied to.
+    fn unprefix(&self, word: &str, add: &str) -> Option<String> {
+        let rest = word.strip_prefix(add).filter(|rest| !rest.is_empty())?;
+        let stem = format!("{}{rest}", self.strip);
+        let chars: Vec<char> = stem.chars().collect();
+
+        self.condition.matches(&chars).then_some(stem)
+    }
+
+    /// Undoes a suffix rule, returning the stem it would have applied to.
+    fn unsuffix(&self, word: &str, add: &str) -> Option<String> {
+        let rest = word.strip_suffix(add).filter(|rest| !rest.is_empty())?;
+        let stem = format!("{rest}{}", self.strip);
+        let chars: Vec<char> = stem.chars().collect();
+        let start = chars.len().checked_sub(self.condition.0.len())?;
+
+        self.condition.matches(&chars[start..]).then_some(stem)
+    }
+}
+
+/// The condition a stem must satisfy, matched against its start or end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Condition(Vec<Pattern>);
+
+/// A single position in a `Condition`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Pattern {
+    Any,
+    Char(char),
+    Class { chars: Vec<char>, negated: bool },
+}
+
+impl Condition {
+    /// Parses a condition like `[^aeiou]y`.
+    fn parse(raw: &str) -> io::Result<Self> {
+        let mut patterns = Vec::new();
+        let mut chars = raw.chars();
+
+        while let Some(ch) = chars.next() {
+            let pattern = match ch {
+                '.' => Pattern::Any,
+                '[' => {
+                    let class: String = chars.by_ref().take_while(|&ch| ch != ']').collect();
+                    let (negated, class) = class
+                        .strip_prefix('^')
+                        .map_or((false, class.as_str()), |class| (true, class));
+                    Pattern::Class {
+                        chars: class.chars().collect(),
+                        negated,
+                    }
+                }
+                ']' => {
+                    let message = format!("Invalid condition: {raw}");
+                    return Err(io::Error::new(InvalidData, message));
+                }
+                _ => Pattern::Char(ch),
+            };
+            patterns.push(pattern);
+        }
+
+        Ok(Self(patterns))
+    }
+
+    /// Whether the leading chars match the condition.
+    fn matches(&self, chars: &[char]) -> bool {
+        chars.len() >= self.0.len()
+            && self.0.iter().zip(chars).all(|(pattern, ch)| match pattern {
+                Pattern::Any => true,
+                Pattern::Char(expected) => expected == ch,
+                Pattern::Class { chars, negated } => chars.contains(ch) != *negated,
+            })
+    }
+}
+
+/// How flags are encoded, set by the `FLAG` directive in an `.aff` file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FlagFormat {
+    Single,
+    Long,
+    Numeric,
+}
+
+impl FlagFormat {
+    /// Reads the flag format from an `.aff` file, defaulting to single chars.
+    fn from_aff(aff: &str) -> Self {
+        aff.lines()
+            .find_map(|line| line.trim().strip_prefix("FLAG "))
+            .map_or(Self::Single, |format| match format.trim() {
+                "long" => Self::Long,
+                "num" => Self::Numeric,
+                _ => Self::Single,
+            })
+    }
+
+    /// Parses a string of flags.
+    fn parse_all(self, raw: &str) -> io::Result<Vec<Flag>> {
+        match self {
+            Self::Single => Ok(raw.chars().map(Flag::from).collect()),
+            Self::Long => {
+                let chars: Vec<char> = raw.chars().collect();
+                Ok(chars
+                    .chunks(2)
+                    .map(|pair| pair.iter().fold(0, |flag, &ch| flag << 16 | Flag::from(ch)))
+                    .collect())
+            }
+            Self::Numeric => raw
+                .split(',')
+                .filter(|flag| !flag.is_empty())
+                .map(|flag| {
+                    flag.parse()
+                        .map_err(|_| io::Error::new(InvalidData, format!("Invalid flag: {flag}")))
+                })
+                .collect(),
+        }
+    }
+
+    /// Parses a single flag.
+    fn parse_one(self, raw: &str) -> io::Result<Flag> {
+        self.parse_all(raw)?
+            .first()
+            .copied()
+            .ok_or_else(|| io::Error::new(InvalidData, "Missing affix flag"))
+    }
+}
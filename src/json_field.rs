@@ -0,0 +1,85 @@
+use serde_json::Value;
+use std::io::{self, BufRead, ErrorKind::InvalidData, Read};
+
+/// Reads the text at a field of each JSON Lines record, one record per line.
+///
+/// Records without a string at the field are skipped, and each extracted
+/// string is followed by a newline so records don't run together.
+pub struct JsonField<R> {
+    /// JSON Lines input.
+    input: R,
+
+    /// JSON Pointer to the field, built from a dotted path like `"meta.text"`.
+    pointer: String,
+
+    /// Text extracted from the current record, not yet read.
+    text: Vec<u8>,
+
+    /// Position of the next unread byte of `text`.
+    position: usize,
+
+    /// Line number of the current record, for reporting invalid JSON.
+    line: usize,
+}
+
+impl<R: BufRead> JsonField<R> {
+    /// Constructs a `JsonField` reader for a dotted field path.
+    pub fn new(input: R, path: &str) -> Self {
+        let pointer = path
+            .split('.')
+            .map(|key| format!("/{}", key.replace('~', "~0").replace('/', "~1")))
+            .collect();
+
+        Self {
+            input,
+            pointer,
+            text: Vec::new(),
+            position: 0,
+            line: 0,
+        }
+    }
+
+    /// Reads records until one has text at the field, returning `false` at the end of input.
+    fn next_text(&mut self) -> io::Result<bool> {
+        let mut record = String::new();
+        loop {
+            record.clear();
+            if self.input.read_line(&mut record)? == 0 {
+                return Ok(false);
+            }
+            self.line += 1;
+            if record.trim().is_empty() {
+                continue;
+            }
+
+            let value: Value = serde_json::from_str(&record).map_err(|err| {
+                io::Error::new(
+                    InvalidData,
+                    format!("Invalid JSON on line {}: {err}", self.line),
+                )
+            })?;
+            if let Some(text) = value.pointer(&self.pointer).and_then(Value::as_str) {
+                self.text.clear();
+                self.text.extend_from_slice(text.as_bytes());
+                self.text.push(b'\n');
+                self.position = 0;
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Read for JsonField<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.text.len() && !self.next_text()? {
+            return Ok(0);
+        }
+
+        let unread = &self.text[self.position..];
+        let len = buf.len().min(unread.len());
+        buf[..len].copy_from_slice(&unread[..len]);
+        self.position += len;
+
+        Ok(len)
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(feature = "hunspell")]
+use crate::Dictionary;
+use crate::Phrases;
+
+/// Word lists, usually loaded from files, that change how words are tallied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Lexicon {
+    /// Multi-word expressions to tally as single entries.
+    pub phrases: Option<Phrases>,
+
+    /// Hunspell dictionary for folding inflected words to their stems.
+    #[cfg(feature = "hunspell")]
+    pub dictionary: Option<Dictionary>,
+}
+
+impl Lexicon {
+    /// Sets the phrases to tally as single entries.
+    pub fn with_phrases(mut self, phrases: Phrases) -> Self {
+        self.phrases = Some(phrases);
+        self
+    }
+
+    /// Sets the dictionary for folding words to their stems.
+    #[cfg(feature = "hunspell")]
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+}
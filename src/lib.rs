@@ -8,15 +8,88 @@
 //! The `unicode-segmentation` Crate segments along "Word Bounaries" according
 //! to the [Unicode Standard Annex #29](http://www.unicode.org/reports/tr29/).
 //!
+//! # `Source`
+//!
+//! A `Source` is a file path, stdin or bytes in memory to `open` for reading.
+//! It's parsed from a string with `TryFrom`, where `"-"` stands for stdin.
+//! A `List` of sources read one after another can be read from a manifest of
+//! NUL- or newline-delimited paths with `read_list`.
+//!
 //! # `Case`, `Sort` and `Filters`
 //!
 //! In addition to source input, a `WordTally` is contstructed with options for
 //! `Case` normalization, `Sort` order and word `Filters`. `Case` options include
 //! `Original` (case sensitive) and `Lower` or `Upper` case normalization. `Sort`
 //! order can be `Unsorted` or sorted `Desc` (descending) or `Asc` (ascending).
-//! A `tally` can be sorted at construction and resorted with the `sort` method.
-//! Sorting doesn't impact the `count` or `uniq_count` fields. `Filter`s can
+//! A `tally` can be sorted at construction, resorted with the `sort` method and
+//! flipped with the `reverse` method.
+//! Sorting doesn't impact the `count` or `uniq_count` fields, but keeping a
+//! band of ranks from the sorted tally with `retain_ranks` does. `Filter`s can
 //! be used to provide list of words that should or shouldn't be tallied.
+//! Near-duplicate words can be merged into their most frequent spelling with
+//! the `fuzzy_merge` option before filters are applied.
+//!
+//! # `Encoding`
+//!
+//! Input is read as UTF-8 by default. Setting another `Encoding` with
+//! `Options::with_encoding` transcodes input to UTF-8 as it's read.
+//!
+//! # `Unit`
+//!
+//! Each occurrence of a word is counted by default. Setting a sentence or
+//! paragraph `Unit` with `Options::with_unit` instead counts the number of
+//! sentences or paragraphs each word appears in.
+//!
+//! # `Lexicon`
+//!
+//! A `Lexicon` holds word lists used while tallying. `Phrases` are multi-word
+//! expressions tallied as single entries. With the `hunspell` feature, a
+//! Hunspell `Dictionary` folds inflected words to their stems. A `WordTally`
+//! is constructed with a `Lexicon` using `WordTally::with_lexicon`.
+//!
+//! # `Script`
+//!
+//! The writing `Script` of a tallied word, and its `Direction`, can be detected
+//! with `Script::of`, and a tally can be split into a tally per script with
+//! `split_by_script`. Invisible bidirectional marks are stripped from words
+//! before they're tallied so right-to-left text tallies consistently. A tally
+//! can also be split by the text a regex matches with `split_by_pattern`.
+//!
+//! # `Vocabulary`
+//!
+//! A `Vocabulary` holds just the distinct normalized and filtered words of an
+//! input, without counting them, for when only the word list is needed.
+//!
+//! # `Readability`
+//!
+//! `WordTally::try_with_readability` also counts the sentences, words and
+//! estimated syllables of the input as it's tallied, to score its
+//! `Readability` with the Flesch-Kincaid and SMOG grades. The `syllables`
+//! estimate for a single word is also used by the `min_syllables` filter.
+//!
+//! # `Baseline`
+//!
+//! A `Baseline` reference frequency list scores the `keyness` of each tallied
+//! word, the signed log-likelihood of its count compared to the baseline corpus.
+//!
+//! # `WordTallyError`
+//!
+//! `WordTally::new` tallies words up to the first read error or invalid UTF-8
+//! line. The `try_new` and `try_with_lexicon` constructors instead return a
+//! `WordTallyError`, which reports the byte offset of input that isn't valid
+//! UTF-8, or in its `Encoding`.
+//! Opening a `Source`, `Baseline` or `Dictionary` also returns a
+//! `WordTallyError`, whose `kind` tells reading and UTF-8 errors apart.
+//! Errors name the file they happened in with `source_path`: a file that
+//! fails to open or read sets it, and `locate` sets it for an offset into a
+//! `Source::List`.
+//!
+//! # `Span`
+//!
+//! Text is segmented into words by writing each word's `Span` into a reused
+//! buffer with `word_spans`, which can also be used to build other
+//! aggregations without allocating for each word. A `Segmenter` set with
+//! `Options::with_segmenter` can split words on whitespace alone instead.
 //!
 //! # Examples
 //!
@@ -29,17 +102,55 @@
 //!
 //! assert_eq!(words.into_tally(), expected_tally);
 //! ```
+use encoding::Decoder;
 use indexmap::IndexMap;
+use regex::Regex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::hash::Hash;
 use std::io::{BufRead, BufReader, Read};
-use unicode_segmentation::UnicodeSegmentation;
+use std::ops::RangeInclusive;
+use std::{mem, str};
 
+pub mod baseline;
+pub mod encoding;
+pub mod error;
 pub mod filters;
+pub mod frozen;
+pub(crate) mod fuzzy;
+#[cfg(feature = "hunspell")]
+pub mod hunspell;
+pub mod lexicon;
 pub mod options;
+pub mod phrases;
+pub mod readability;
+pub mod script;
+pub mod segment;
+pub mod source;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod unit;
+pub mod vocabulary;
 
-pub use filters::{ExcludeWords, Filters, MinChars, MinCount};
+pub use baseline::Baseline;
+pub use encoding::Encoding;
+pub use error::{ErrorKind, WordTallyError};
+pub use filters::{
+    Denoise, ExcludeWords, Filters, LengthUnit, MinChars, MinCount, MinSyllables, WordTypes,
+};
+pub use frozen::FrozenTally;
+#[cfg(feature = "hunspell")]
+pub use hunspell::Dictionary;
+pub use lexicon::Lexicon;
 pub use options::{Case, Options, Sort};
+pub use phrases::Phrases;
+pub use readability::{syllables, Readability};
+pub use script::{Direction, Script};
+pub use segment::{word_spans, Segmenter, Span, WordType};
+pub use source::Source;
+pub use unit::Unit;
+pub use vocabulary::Vocabulary;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
@@ -49,9 +160,11 @@ pub struct WordTally {
     tally: Box<[(Box<str>, usize)]>,
 
     /// Word tallying options like case normalization and sort order.
+    #[cfg_attr(feature = "serde", serde(skip))]
     options: Options,
 
     /// Filters that limit words from being tallied.
+    #[cfg_attr(feature = "serde", serde(skip))]
     filters: Filters,
 
     /// The sum of all words tallied.
@@ -71,8 +184,151 @@ impl From<WordTally> for Vec<(Box<str>, usize)> {
 /// `WordTally` fields are eagerly populated upon construction and exposed by getter methods.
 impl WordTally {
     /// Constructs a new `WordTally` from a source that implements `Read` like file or stdin.
+    ///
+    /// Tallying stops at the first read error or invalid UTF-8 line. Use
+    /// `try_new` to get the error instead.
     pub fn new<T: Read>(input: T, options: Options, filters: Filters) -> Self {
-        let mut tally_map = Self::tally_map(input, options.case);
+        Self::with_lexicon(input, options, filters, &Lexicon::default())
+    }
+
+    /// Constructs a new `WordTally` that also tallies words using a `Lexicon` of word lists.
+    pub fn with_lexicon<T: Read>(
+        input: T,
+        options: Options,
+        filters: Filters,
+        lexicon: &Lexicon,
+    ) -> Self {
+        let mut tally_map = IndexMap::new();
+        // Words read before an error are still tallied, as documented on `new`.
+        let _error = Self::tally_map(input, &options, lexicon, &mut tally_map, None);
+
+        Self::from_tally_map(tally_map, options, filters, lexicon)
+    }
+
+    /// Constructs a new `WordTally`, returning an error if the input can't be read as UTF-8.
+    pub fn try_new<T: Read>(
+        input: T,
+        options: Options,
+        filters: Filters,
+    ) -> Result<Self, WordTallyError> {
+        Self::try_with_lexicon(input, options, filters, &Lexicon::default())
+    }
+
+    /// Constructs a new `WordTally` with a `Lexicon`, returning an error if the input can't be read as UTF-8.
+    pub fn try_with_lexicon<T: Read>(
+        input: T,
+        options: Options,
+        filters: Filters,
+        lexicon: &Lexicon,
+    ) -> Result<Self, WordTallyError> {
+        let mut tally_map = IndexMap::new();
+        Self::tally_map(input, &options, lexicon, &mut tally_map, None)?;
+
+        Ok(Self::from_tally_map(tally_map, options, filters, lexicon))
+    }
+
+    /// Constructs a new `WordTally` with a `Lexicon`, also measuring the `Readability` of the input.
+    ///
+    /// Returns an error if the input can't be read as UTF-8.
+    pub fn try_with_readability<T: Read>(
+        input: T,
+        options: Options,
+        filters: Filters,
+        lexicon: &Lexicon,
+    ) -> Result<(Self, Readability), WordTallyError> {
+        let mut tally_map = IndexMap::new();
+        let mut readability = Readability::default();
+        Self::tally_map(
+            input,
+            &options,
+            lexicon,
+            &mut tally_map,
+            Some(&mut readability),
+        )?;
+
+        Ok((
+            Self::from_tally_map(tally_map, options, filters, lexicon),
+            readability,
+        ))
+    }
+
+    /// Constructs a `WordTally` for each document in an input, where documents
+    /// are separated by lines that are exactly `separator`.
+    ///
+    /// Returns an error if the input can't be read as UTF-8, with its offset in the whole input.
+    pub fn try_documents<T: Read>(
+        input: T,
+        separator: &str,
+        options: Options,
+        filters: &Filters,
+        lexicon: &Lexicon,
+    ) -> Result<Vec<Self>, WordTallyError> {
+        let mut reader = BufReader::new(options.encoding.decode(input));
+        let decoded = options.with_encoding(Encoding::Utf8);
+        let limit = options.max_buffer.unwrap_or(usize::MAX);
+        let mut documents = Vec::new();
+        let mut document = Vec::new();
+        let mut document_offset = 0;
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            let line_offset = document_offset + document.len();
+            let len = Self::read_line(&mut reader, &mut line, limit, line_offset)?;
+            let content = line.strip_suffix(b"\n").unwrap_or(&line);
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            let is_separator = len > 0 && content == separator.as_bytes();
+            if !is_separator && len > 0 {
+                if document.len() + len > limit {
+                    let offset = line_offset + reader.get_ref().bom_len();
+                    return Err(WordTallyError::too_large(offset, limit));
+                }
+                document.extend_from_slice(&line);
+                continue;
+            }
+
+            if len == 0 && document.is_empty() && !documents.is_empty() {
+                break;
+            }
+            let mut tally_map = IndexMap::new();
+            Self::tally_map(&document[..], &decoded, lexicon, &mut tally_map, None)
+                .map_err(|err| err.offset_by(document_offset + reader.get_ref().bom_len()))?;
+            documents.push(Self::from_tally_map(
+                tally_map,
+                options,
+                filters.clone(),
+                lexicon,
+            ));
+            if len == 0 {
+                break;
+            }
+            document_offset += document.len() + len;
+            document.clear();
+        }
+
+        Ok(documents)
+    }
+
+    /// Constructs a `WordTally` from a map of words and counts, applying options and filters.
+    fn from_tally_map(
+        tally_map: IndexMap<Box<str>, usize>,
+        options: Options,
+        filters: Filters,
+        #[cfg_attr(not(feature = "hunspell"), allow(unused_variables))] lexicon: &Lexicon,
+    ) -> Self {
+        // Words counted once per unit were already folded by `tally_map`.
+        #[cfg(feature = "hunspell")]
+        let tally_map = match &lexicon.dictionary {
+            Some(dictionary) if options.unit == Unit::Word => {
+                Self::fold_stems(tally_map, options.case, dictionary)
+            }
+            _ => tally_map,
+        };
+        let mut tally_map = tally_map;
+
+        if let Some(max_distance) = options.fuzzy_merge {
+            fuzzy::merge(&mut tally_map, max_distance);
+        }
         filters.apply(&mut tally_map, options.case);
 
         let count = tally_map.values().sum();
@@ -95,6 +351,101 @@ impl WordTally {
         sort.apply(self);
     }
 
+    /// Suggests a `MinCount` at the knee of the frequency spectrum, if it has one.
+    ///
+    /// Each distinct count is a candidate threshold. The knee is the threshold
+    /// where the unique words it keeps fall furthest below a straight line from
+    /// the lowest to the highest threshold, past which raising it trims few words.
+    pub fn suggest_min_count(&self) -> Option<usize> {
+        let mut counts: Vec<usize> = self.tally.iter().map(|&(_, count)| count).collect();
+        counts.sort_unstable();
+        let mut thresholds = counts.clone();
+        thresholds.dedup();
+
+        let (&lowest, &highest) = (thresholds.first()?, thresholds.last()?);
+        if lowest == highest {
+            return None;
+        }
+        let kept = |threshold| counts.len() - counts.partition_point(|&count| count < threshold);
+        let (most_kept, least_kept) = (kept(lowest), kept(highest));
+
+        let mut knee = None;
+        let mut knee_gap = 0.0;
+        for threshold in thresholds {
+            let x = (threshold - lowest) as f64 / (highest - lowest) as f64;
+            let y = (kept(threshold) - least_kept) as f64 / (most_kept - least_kept) as f64;
+            let gap = 1.0 - x - y;
+            if gap > knee_gap {
+                knee = Some(threshold);
+                knee_gap = gap;
+            }
+        }
+
+        knee
+    }
+
+    /// Reverses the order of the `tally` field, independent of its `Sort` order.
+    pub fn reverse(&mut self) {
+        self.tally.reverse();
+    }
+
+    /// Keeps only words whose rank, counting from 1 in the current sort order, is within `ranks`.
+    pub fn retain_ranks(&mut self, ranks: RangeInclusive<usize>) {
+        let len = self.tally.len();
+        let start = ranks.start().saturating_sub(1).min(len);
+        let end = (*ranks.end()).clamp(start, len);
+
+        let mut tally = mem::take(&mut self.tally).into_vec();
+        tally.truncate(end);
+        tally.drain(..start);
+        self.count = tally.iter().map(|&(_, count)| count).sum();
+        self.uniq_count = tally.len();
+        self.tally = tally.into_boxed_slice();
+    }
+
+    /// Splits the tally into a separate tally for each `Script`, ordered by first appearance.
+    pub fn split_by_script(self) -> IndexMap<Script, Self> {
+        self.split_by(|word| Some(Script::of(word)))
+    }
+
+    /// Splits the tally into a separate tally for each text `pattern` matches, ordered by first appearance.
+    ///
+    /// Words are grouped by the first capture group if the pattern has one, or
+    /// by the whole match otherwise. Words the pattern doesn't match are dropped.
+    pub fn split_by_pattern(self, pattern: &Regex) -> IndexMap<Box<str>, Self> {
+        self.split_by(|word| {
+            let captures = pattern.captures(word)?;
+            let key = captures.get(1).or_else(|| captures.get(0))?;
+
+            Some(Box::from(key.as_str()))
+        })
+    }
+
+    /// Splits the tally by a key for each word, dropping words without one.
+    fn split_by<K: Hash + Eq>(self, key: impl Fn(&str) -> Option<K>) -> IndexMap<K, Self> {
+        let mut groups: IndexMap<K, Vec<(Box<str>, usize)>> = IndexMap::new();
+        for (word, count) in self.tally.into_vec() {
+            if let Some(key) = key(&word) {
+                groups.entry(key).or_default().push((word, count));
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, tally)| {
+                let word_tally = Self {
+                    count: tally.iter().map(|&(_, count)| count).sum(),
+                    uniq_count: tally.len(),
+                    tally: tally.into_boxed_slice(),
+                    options: self.options,
+                    filters: self.filters.clone(),
+                };
+
+                (key, word_tally)
+            })
+            .collect()
+    }
+
     /// Gets the `tally` field.
     pub const fn tally(&self) -> &[(Box<str>, usize)] {
         &self.tally
@@ -125,17 +476,166 @@ impl WordTally {
         self.count
     }
 
-    /// Creates a tally of normalized words from an input that implements `Read`.
-    fn tally_map<T: Read>(input: T, case: Case) -> IndexMap<Box<str>, usize> {
-        let mut tally = IndexMap::new();
-        let lines = BufReader::new(input).lines();
+    /// Tallies normalized words, and any `Phrases`, from an input that implements `Read`.
+    ///
+    /// With a `Unit` other than `Unit::Word`, a word is counted once per unit it
+    /// appears in, so words are folded into their `Dictionary` stem, if there
+    /// is one, before they're checked against the unit.
+    fn tally_map<T: Read>(
+        input: T,
+        options: &Options,
+        lexicon: &Lexicon,
+        tally: &mut IndexMap<Box<str>, usize>,
+        readability: Option<&mut Readability>,
+    ) -> Result<(), WordTallyError> {
+        let mut seen = HashSet::new();
+        let mut current = 0;
 
-        for line in lines.map_while(Result::ok) {
-            line.unicode_words().for_each(|word| {
-                *tally.entry(case.normalize(word)).or_insert(0) += 1;
+        Self::read_words(input, options, lexicon, readability, |word, unit| {
+            #[cfg(feature = "hunspell")]
+            let word = match &lexicon.dictionary {
+                Some(dictionary) if options.unit != Unit::Word => {
+                    Self::fold_stem(word, options.case, dictionary)
+                }
+                _ => word,
+            };
+            if options.unit != Unit::Word {
+                if unit != current {
+                    seen.clear();
+                    current = unit;
+                }
+                if !seen.insert(word.clone()) {
+                    return;
+                }
+            }
+            *tally.entry(word).or_insert(0) += 1;
+        })
+    }
+
+    /// Reads normalized words, and any `Phrases`, from an input, passing each
+    /// one and the index of the `Unit` it's in to `add`.
+    ///
+    /// If a `Readability` is given, the sentences read are also added to it.
+    ///
+    /// Words are read up to the first read error or invalid UTF-8 line, which
+    /// is returned with its absolute byte offset in the input.
+    pub(crate) fn read_words<T: Read>(
+        input: T,
+        options: &Options,
+        lexicon: &Lexicon,
+        readability: Option<&mut Readability>,
+        mut add: impl FnMut(Box<str>, usize),
+    ) -> Result<(), WordTallyError> {
+        let mut matcher = lexicon
+            .phrases
+            .as_ref()
+            .map(|phrases| phrases.matcher(options));
+        let mut reader = BufReader::new(options.encoding.decode(input));
+        let limit = options.max_buffer.unwrap_or(usize::MAX);
+        let mut units = options.unit.units();
+        let mut profile =
+            readability.map(|readability| (readability, Unit::Sentence.units(), Vec::new()));
+        let mut bytes = Vec::new();
+        let mut spans = Vec::new();
+        let mut offset = 0;
+        let mut current = 0;
+
+        let mut add_words = |text: &str, unit: usize| {
+            if let Some(matcher) = matcher.as_mut().filter(|_| unit != current) {
+                matcher.flush(&mut |word| add(word, current));
+            }
+            current = unit;
+
+            options.words(text, &mut spans, |word| match &mut matcher {
+                Some(matcher) => matcher.push(word, &mut |word| add(word, unit)),
+                None => add(word, unit),
             });
+        };
+
+        let result = loop {
+            bytes.clear();
+            let len = match Self::read_line(&mut reader, &mut bytes, limit, offset) {
+                Ok(0) => break Ok(()),
+                Ok(len) => len,
+                Err(err) => break Err(err),
+            };
+            // The byte order mark is only known once reading has started.
+            let line_offset = offset + reader.get_ref().bom_len();
+            let line = match str::from_utf8(&bytes) {
+                Ok(line) => line,
+                Err(err) => break Err(WordTallyError::utf8(line_offset, &bytes, err)),
+            };
+
+            units.push_line(line, &mut add_words);
+            let mut buffered = units.buffered();
+            if let Some((readability, sentences, spans)) = &mut profile {
+                sentences.push_line(line, |sentence, _| {
+                    readability.add_sentence(sentence, options.segmenter, spans);
+                });
+                buffered = buffered.max(sentences.buffered());
+            }
+            if buffered > limit {
+                let offset = line_offset;
+                break Err(WordTallyError::too_large(offset, limit));
+            }
+            offset += len;
+        };
+        units.finish(&mut add_words);
+        if let Some((readability, sentences, spans)) = &mut profile {
+            sentences.finish(|sentence, _| {
+                readability.add_sentence(sentence, options.segmenter, spans);
+            });
+        }
+
+        if let Some(matcher) = matcher {
+            matcher.finish(&mut |word| add(word, current));
         }
 
-        tally
+        result
+    }
+
+    /// Reads a line starting at `offset` in the decoded input like `read_until`,
+    /// returning a `TooLarge` error if it's longer than `limit` bytes.
+    ///
+    /// Errors decoding the input already have their offset in the input.
+    fn read_line<R: Read>(
+        reader: &mut BufReader<Decoder<R>>,
+        line: &mut Vec<u8>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<usize, WordTallyError> {
+        let most = u64::try_from(limit).unwrap_or(u64::MAX).saturating_add(1);
+        let len = reader.take(most).read_until(b'\n', line)?;
+        if len > limit {
+            let offset = offset + reader.get_ref().bom_len();
+            return Err(WordTallyError::too_large(offset, limit));
+        }
+
+        Ok(len)
+    }
+
+    /// Folds each word in a tally into its `Dictionary` stem, summing their counts.
+    #[cfg(feature = "hunspell")]
+    fn fold_stems(
+        tally_map: IndexMap<Box<str>, usize>,
+        case: Case,
+        dictionary: &Dictionary,
+    ) -> IndexMap<Box<str>, usize> {
+        let mut folded = IndexMap::new();
+        for (word, count) in tally_map {
+            *folded
+                .entry(Self::fold_stem(word, case, dictionary))
+                .or_insert(0) += count;
+        }
+
+        folded
+    }
+
+    /// Folds a word into its `Dictionary` stem, or keeps it if it has none.
+    #[cfg(feature = "hunspell")]
+    fn fold_stem(word: Box<str>, case: Case, dictionary: &Dictionary) -> Box<str> {
+        dictionary
+            .stem(&word)
+            .map_or(word, |stem| case.normalize(stem))
     }
 }
@@ -1,42 +1,400 @@
 //! `word-tally` tallies and outputs the count of words from a given input.
 
 pub(crate) mod args;
-pub(crate) mod input;
+pub(crate) mod columns;
+pub(crate) mod exit_code;
+#[cfg(feature = "serde")]
+pub(crate) mod json_field;
+pub(crate) mod memory;
 pub(crate) mod output;
 pub(crate) mod verbose;
+pub(crate) mod width;
 
-use anyhow::Result;
-use args::Args;
-use clap::Parser;
-use input::Input;
+use anyhow::{Context, Result};
+use args::{Args, Empty, GroupBy, TopPer};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use columns::Columns;
+use exit_code::EmptyInput;
+use indexmap::IndexMap;
+#[cfg(feature = "serde")]
+use json_field::JsonField;
 use output::Output;
+use std::fmt::Display;
+use std::fs;
+#[cfg(feature = "serde")]
+use std::io::{BufReader, Read};
+use std::process::ExitCode;
 use unescaper::unescape;
-use verbose::Verbose;
-use word_tally::{Filters, Options, WordTally};
+use verbose::{Verbose, Verbosity};
+#[cfg(feature = "hunspell")]
+use word_tally::Dictionary;
+use word_tally::{
+    Baseline, Case, Encoding, ExcludeWords, Filters, Lexicon, Options, Phrases, Readability, Sort,
+    Source, Vocabulary, WordTally, WordTallyError,
+};
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     let args = Args::parse();
+    let exit_codes = args.exit_codes;
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            ExitCode::from(exit_codes.code(&err))
+        }
+    }
+}
+
+/// Tallies words from the input given by command-line arguments and writes the output.
+fn run(args: Args) -> Result<()> {
+    let verbosity = match args.verbose_output {
+        Some(_) => args.verbosity().max(Verbosity::Verbose),
+        None => args.verbosity(),
+    };
     let delimiter = unescape(&args.delimiter)?;
-    let input = Input::from_args(&args.input)?;
-    let source = input.source();
+    let source = source(&args)?;
+    let reader = match args.byte_range() {
+        Some(_) if args.encoding != Encoding::Utf8 => Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--byte-offset and --byte-limit need UTF-8 input, not {}",
+                    args.encoding
+                ),
+            )
+            .exit(),
+        Some(range) => source.open_range(range),
+        None => source.open(),
+    }
+    .with_context(|| format!("Failed to read from {source}"))?;
+    let lexicon = lexicon(&args)?;
+    let options = Options::new(args.case, args.sort)
+        .with_fuzzy_merge(args.fuzzy_merge)
+        .with_fold_possessives(args.fold_possessives)
+        .with_encoding(args.encoding)
+        .with_segmenter(args.segmenter)
+        .with_unit(args.unit)
+        .with_max_buffer(args.max_buffer);
+    #[cfg(feature = "serde")]
+    let options = options_json(&args, options)?;
+    // Records are decoded before their field is extracted, then tallied as UTF-8.
+    #[cfg(feature = "serde")]
+    let (reader, options): (Box<dyn Read>, _) = match &args.json_field {
+        Some(path) => {
+            let decoded = BufReader::new(options.encoding.transcode(reader));
+            let options = options.with_encoding(Encoding::Utf8);
+            (Box::new(JsonField::new(decoded, path)), options)
+        }
+        None => (reader, options),
+    };
+    let filters = filters(&args, options.case, verbosity)?;
 
-    let reader = input.get_reader(&source)?;
-    let options = Options::new(args.case, args.sort);
-    let filters = Filters::new(&args.min_chars, &args.min_count, args.exclude);
+    if args.unique {
+        let mut vocabulary = Vocabulary::try_new(reader, &options, &filters, &lexicon)
+            .map_err(|err| tally_error(&args, &source, err))?;
+        check_empty(args.empty, verbosity, &source, vocabulary.is_empty())?;
 
-    let word_tally = WordTally::new(reader, options, filters);
+        return write_vocabulary(&args, &mut vocabulary);
+    }
+
+    if let Some(separator) = &args.doc_separator {
+        let documents = WordTally::try_documents(reader, separator, options, &filters, &lexicon)
+            .map_err(|err| tally_error(&args, &source, err))?;
+        let is_empty = documents.iter().all(|document| document.count() == 0);
+        check_empty(args.empty, verbosity, &source, is_empty)?;
+        let baseline = baseline(&args, options.case)?;
+        // Each document's counts are normalized and scored against its own total.
+        let columns =
+            |tally: &WordTally| columns(&args, &delimiter, tally.count(), baseline.as_ref());
 
-    if args.verbose {
-        let stderr = Output::stderr();
-        let mut verbose = Verbose::new(stderr, &word_tally, &delimiter, &source);
+        let mut output = Output::from_args(&args.output)?;
+        let groups = (1..).zip(documents).collect();
+        write_groups(&mut output, groups, columns, |tally| arrange(&args, tally))?;
+        return output.flush();
+    }
+
+    let (mut word_tally, readability) = if args.readability {
+        WordTally::try_with_readability(reader, options, filters, &lexicon)
+            .map(|(word_tally, readability)| (word_tally, Some(readability)))
+    } else {
+        WordTally::try_with_lexicon(reader, options, filters, &lexicon)
+            .map(|word_tally| (word_tally, None))
+    }
+    .map_err(|err| tally_error(&args, &source, err))?;
+    check_empty(args.empty, verbosity, &source, word_tally.count() == 0)?;
+    let total = word_tally.count();
+    if let Some(ranks) = args.rank_range.clone() {
+        word_tally.retain_ranks(ranks);
+    }
+
+    if args.suggest_min_count {
+        let suggestion = word_tally
+            .suggest_min_count()
+            .map_or_else(|| "none".to_string(), |min_count| min_count.to_string());
+        let mut stderr = Output::stderr();
+        stderr.write_line(&format!("suggested-min-count{delimiter}{suggestion}\n"))?;
+    }
+
+    if let Some(readability) = readability {
+        write_readability(&readability, &delimiter, args.precision)?;
+    }
+
+    if verbosity >= Verbosity::Verbose {
+        let output = match &args.verbose_output {
+            Some(path) => Output::file(path.clone())?,
+            None => Output::stderr(),
+        };
+        let mut verbose = Verbose::new(output, verbosity, &word_tally, &delimiter, &source);
         verbose.log()?;
     }
 
+    let baseline = baseline(&args, options.case)?;
+    let columns = || columns(&args, &delimiter, total, baseline.as_ref());
+    let arrange = |tally: &mut WordTally| arrange(&args, tally);
+
+    let mut output = Output::from_args(&args.output)?;
+    match (args.group_by, &args.top_per) {
+        (Some(GroupBy::Script), _) => {
+            let groups = word_tally.split_by_script();
+            write_groups(&mut output, groups, |_| columns(), arrange)?;
+        }
+        (None, Some(TopPer { pattern, count })) => {
+            let mut groups = word_tally.split_by_pattern(pattern);
+            for tally in groups.values_mut() {
+                tally.retain_ranks(1..=*count);
+            }
+            write_groups(&mut output, groups, |_| columns(), arrange)?;
+        }
+        (None, None) => {
+            arrange(&mut word_tally);
+            columns().write(&mut output, word_tally.tally())?;
+        }
+    }
+    output.flush()?;
+
+    Ok(())
+}
+
+/// Loads the baseline to score keyness against, if one is given.
+fn baseline(args: &Args, case: Case) -> Result<Option<Baseline>> {
+    args.baseline
+        .as_ref()
+        .map(|path| {
+            Baseline::open(path, case)
+                .with_context(|| format!("Failed to read baseline: {}", path.display()))
+        })
+        .transpose()
+}
+
+/// Sets up the output `Columns` from arguments.
+const fn columns<'a>(
+    args: &Args,
+    delimiter: &'a str,
+    total: usize,
+    baseline: Option<&'a Baseline>,
+) -> Columns<'a> {
+    Columns::new(delimiter, total)
+        .with_script(args.with_script)
+        .with_syllables(args.with_syllables)
+        .with_baseline(baseline)
+        .with_align(args.align)
+        .with_normalize(args.normalize_counts)
+        .with_precision(args.precision)
+        .with_max_word_width(args.max_word_display)
+}
+
+/// Reverses a tally and keeps the skipped and limited ranks, if arguments ask to.
+fn arrange(args: &Args, tally: &mut WordTally) {
+    if args.reverse {
+        tally.reverse();
+    }
+    if args.skip > 0 || args.limit.is_some() {
+        let end = args
+            .limit
+            .map_or(usize::MAX, |limit| args.skip.saturating_add(limit));
+        tally.retain_ranks(args.skip.saturating_add(1)..=end);
+    }
+}
+
+/// Adds the source to a tally error, locating the listed file an error at an offset is in.
+///
+/// Offsets can only be located in the input as it's read, so not within a byte range or JSON field.
+fn tally_error(args: &Args, source: &Source, err: WordTallyError) -> anyhow::Error {
+    let as_read = args.byte_range().is_none();
+    #[cfg(feature = "serde")]
+    let as_read = as_read && args.json_field.is_none();
+
+    let err = match source {
+        Source::List(_) if as_read => err.locate(source),
+        _ => err,
+    };
+    anyhow::Error::new(err).context(format!("Failed to tally words from {source}"))
+}
+
+/// Warns about or fails on an empty tally, depending on the `Empty` action.
+fn check_empty(empty: Empty, verbosity: Verbosity, source: &Source, is_empty: bool) -> Result<()> {
+    if !is_empty {
+        return Ok(());
+    }
+
+    let source = source.to_string();
+    match empty {
+        Empty::Ok => {}
+        Empty::Warn if verbosity > Verbosity::Quiet => {
+            eprintln!("Warning: No words tallied from {source}");
+        }
+        Empty::Warn => {}
+        Empty::Fail => return Err(EmptyInput { source }.into()),
+    }
+
+    Ok(())
+}
+
+/// Writes each distinct word on its own line, sorted unless unsorted and arranged by arguments.
+fn write_vocabulary(args: &Args, vocabulary: &mut Vocabulary) -> Result<()> {
+    if args.sort != Sort::Unsorted {
+        vocabulary.sort();
+    }
+    if args.reverse {
+        vocabulary.reverse();
+    }
+    let end = args
+        .limit
+        .map_or(usize::MAX, |limit| args.skip.saturating_add(limit));
+
     let mut output = Output::from_args(&args.output)?;
-    for (word, count) in word_tally.tally() {
-        output.write_line(&format!("{word}{delimiter}{count}\n"))?;
+    for word in vocabulary.words().iter().take(end).skip(args.skip) {
+        let word = width::truncate(word, args.max_word_display.unwrap_or(usize::MAX));
+        output.write_line(&format!("{word}\n"))?;
     }
     output.flush()?;
 
     Ok(())
 }
+
+/// Writes readability scores to stderr, or `"none"` for input without sentences.
+fn write_readability(readability: &Readability, delimiter: &str, precision: usize) -> Result<()> {
+    let mut stderr = Output::stderr();
+    let scores = [
+        (
+            "average-sentence-length",
+            readability.average_sentence_length(),
+        ),
+        ("flesch-kincaid-grade", readability.flesch_kincaid_grade()),
+        ("smog", readability.smog()),
+    ];
+    for (label, score) in scores {
+        let score = score.map_or_else(
+            || "none".to_string(),
+            |score| format!("{score:.precision$}"),
+        );
+        stderr.write_line(&format!("{label}{delimiter}{score}\n"))?;
+    }
+
+    Ok(())
+}
+
+/// Replaces `Options` with any read from a JSON file, and saves them to one if asked.
+#[cfg(feature = "serde")]
+fn options_json(args: &Args, options: Options) -> Result<Options> {
+    let options = match &args.options_in {
+        Some(path) => {
+            let json = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read options: {}", path.display()))?;
+            serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse options: {}", path.display()))?
+        }
+        None => options,
+    };
+
+    if let Some(path) = &args.options_out {
+        let json = serde_json::to_string_pretty(&options)?;
+        fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write options: {}", path.display()))?;
+    }
+
+    Ok(options)
+}
+
+/// Gets the input `Source`, reading a list of sources if one is given.
+fn source(args: &Args) -> Result<Source> {
+    match &args.files0_from {
+        Some(path) => {
+            let list = Source::try_from(path.as_str())?;
+            list.open()
+                .and_then(|input| Source::read_list(input, args.max_buffer))
+                .with_context(|| format!("Failed to read source list from {list}"))
+        }
+        None => Ok(Source::try_from(args.input.as_str())?),
+    }
+}
+
+/// Builds the word `Filters` from arguments, warning about dropped exclude words.
+fn filters(args: &Args, case: Case, verbosity: Verbosity) -> Result<Filters> {
+    let mut entries = args.exclude.clone();
+    if let Some(path) = &args.exclude_from {
+        let words = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read exclude words: {}", path.display()))?;
+        entries
+            .get_or_insert_with(Vec::new)
+            .extend(words.lines().map(String::from));
+    }
+    let exclude = entries.map(|entries| {
+        let (exclude, dropped) = ExcludeWords::parse(entries, case);
+        if dropped > 0 && verbosity > Verbosity::Quiet {
+            eprintln!("Warning: Ignored {dropped} empty or duplicate exclude words");
+        }
+        exclude
+    });
+
+    Ok(Filters::new(&args.min_chars, &args.min_count, None)
+        .with_exclude(exclude)
+        .with_length_unit(args.length_unit)
+        .with_min_syllables(args.min_syllables)
+        .with_denoise(args.denoise)
+        .with_word_types(args.word_types.clone()))
+}
+
+/// Loads the word lists given as file paths into a `Lexicon`.
+fn lexicon(args: &Args) -> Result<Lexicon> {
+    let mut lexicon = Lexicon::default();
+
+    if let Some(path) = &args.phrases {
+        let phrases = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read phrases: {}", path.display()))?;
+        lexicon = lexicon.with_phrases(Phrases::new(phrases.lines()));
+    }
+
+    #[cfg(feature = "hunspell")]
+    if let Some(path) = &args.hunspell {
+        let dictionary = Dictionary::open(path)
+            .with_context(|| format!("Failed to read dictionary: {}", path.display()))?;
+        lexicon = lexicon.with_dictionary(dictionary);
+    }
+
+    Ok(lexicon)
+}
+
+/// Writes each group's arranged tally under a "[group]" header, separated by blank lines.
+///
+/// The `Columns` for each group are set up from its tally before it's arranged.
+fn write_groups<'a, K: Display>(
+    output: &mut Output,
+    groups: IndexMap<K, WordTally>,
+    columns: impl Fn(&WordTally) -> Columns<'a>,
+    arrange: impl Fn(&mut WordTally),
+) -> Result<()> {
+    for (index, (group, mut tally)) in groups.into_iter().enumerate() {
+        let columns = columns(&tally);
+        arrange(&mut tally);
+        if index > 0 {
+            output.write_line("\n")?;
+        }
+        output.write_line(&format!("[{group}]\n"))?;
+        columns.write(output, tally.tally())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(unix)]
+use std::mem::MaybeUninit;
+
+/// Gets the peak resident set size of this process in bytes, if the platform reports it.
+///
+/// On Linux this is the `VmHWM` high-water mark from `/proc/self/status`. On
+/// other Unix platforms like macOS, or without procfs, it's the `ru_maxrss`
+/// reported by `getrusage`.
+#[cfg(unix)]
+pub fn peak_rss() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    if let Some(high_water_mark) = vm_hwm() {
+        return Some(high_water_mark);
+    }
+
+    max_rss()
+}
+
+/// Gets the peak resident set size of this process in bytes, if the platform reports it.
+#[cfg(not(unix))]
+pub const fn peak_rss() -> Option<u64> {
+    None
+}
+
+/// Gets the `VmHWM` high-water mark in bytes from procfs.
+#[cfg(target_os = "linux")]
+fn vm_hwm() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kibibytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    Some(kibibytes * 1024)
+}
+
+/// Gets the `ru_maxrss` of this process in bytes from `getrusage`.
+#[cfg(unix)]
+fn max_rss() -> Option<u64> {
+    let mut usage = MaybeUninit::<libc::rusage>::zeroed();
+    // SAFETY: `getrusage` fills in the `rusage` it's pointed to, and it's only read if that succeeds.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let max_rss = u64::try_from(usage.ru_maxrss).ok()?;
+
+    // Apple platforms report bytes, and others kibibytes.
+    if cfg!(target_vendor = "apple") {
+        Some(max_rss)
+    } else {
+        Some(max_rss * 1024)
+    }
+}
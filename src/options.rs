@@ -1,23 +1,136 @@
-use crate::WordTally;
+use crate::script::strip_bidi_controls;
+use crate::{Encoding, Segmenter, Span, Unit, WordTally};
 use clap::ValueEnum;
-use core::cmp::Reverse;
 use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Tallying options.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
 pub struct Options {
     pub case: Case,
     pub sort: Sort,
+
+    /// Maximum edit distance for merging near-duplicate words, if any.
+    ///
+    /// The CLI caps this at 3, since the variants indexed per word grow combinatorially with it.
+    /// Words are only merged within one edit per 4 chars, and long words aren't merged.
+    pub fuzzy_merge: Option<usize>,
+
+    /// Whether possessive `'s` endings are stripped so `"dog's"` tallies as `"dog"`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fold_possessives: bool,
+
+    /// Text encoding the input is transcoded from.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub encoding: Encoding,
+
+    /// How text is segmented into words.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub segmenter: Segmenter,
+
+    /// The span of text a word is counted once per.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unit: Unit,
+
+    /// Most bytes of input held in memory at once, if limited.
+    ///
+    /// Input is streamed, but a line is read whole, as is a paragraph when
+    /// counting per sentence or paragraph and a document when splitting on a
+    /// separator. Going over the limit is a `WordTallyError::TooLarge`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_buffer: Option<usize>,
 }
 
 /// Construct `Options`.
 impl Options {
     pub const fn new(case: Case, sort: Sort) -> Self {
-        Self { case, sort }
+        Self {
+            case,
+            sort,
+            fuzzy_merge: None,
+            fold_possessives: false,
+            encoding: Encoding::Utf8,
+            segmenter: Segmenter::Unicode,
+            unit: Unit::Word,
+            max_buffer: None,
+        }
+    }
+
+    /// Sets the maximum edit distance for merging near-duplicate words.
+    pub const fn with_fuzzy_merge(self, max_distance: Option<usize>) -> Self {
+        Self {
+            fuzzy_merge: max_distance,
+            ..self
+        }
+    }
+
+    /// Sets whether possessive `'s` endings are stripped from words.
+    pub const fn with_fold_possessives(self, fold_possessives: bool) -> Self {
+        Self {
+            fold_possessives,
+            ..self
+        }
+    }
+
+    /// Sets the text encoding the input is transcoded from.
+    pub const fn with_encoding(self, encoding: Encoding) -> Self {
+        Self { encoding, ..self }
     }
+
+    /// Sets how text is segmented into words.
+    pub const fn with_segmenter(self, segmenter: Segmenter) -> Self {
+        Self { segmenter, ..self }
+    }
+
+    /// Sets the span of text a word is counted once per.
+    pub const fn with_unit(self, unit: Unit) -> Self {
+        Self { unit, ..self }
+    }
+
+    /// Sets the most bytes of input held in memory at once.
+    pub const fn with_max_buffer(self, max_buffer: Option<usize>) -> Self {
+        Self { max_buffer, ..self }
+    }
+
+    /// Applies the word transforms enabled by these options, then normalizes case.
+    pub fn normalize(&self, word: &str) -> Box<str> {
+        let word = if self.fold_possessives {
+            strip_possessive(word)
+        } else {
+            word
+        };
+
+        self.case.normalize(word)
+    }
+
+    /// Passes each word of text to `each`, segmented and normalized as it's tallied.
+    ///
+    /// Bidi controls are stripped before normalizing, and words left empty are skipped.
+    pub(crate) fn words(&self, text: &str, spans: &mut Vec<Span>, mut each: impl FnMut(Box<str>)) {
+        self.segmenter.spans(text, spans);
+        for span in spans.iter() {
+            let word = self.normalize(&strip_bidi_controls(span.word(text)));
+            if !word.is_empty() {
+                each(word);
+            }
+        }
+    }
+}
+
+/// Strips a trailing possessive `'s`, with either a straight or curly apostrophe.
+fn strip_possessive(word: &str) -> &str {
+    ["'s", "'S", "\u{2019}s", "\u{2019}S"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or(word)
 }
 
 /// Word case normalization options.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
 pub enum Case {
     Original,
@@ -50,6 +163,15 @@ impl Display for Case {
 }
 
 /// Sort order by count.
+///
+/// Words with the same count are ordered by word, so ties sort the same way
+/// on every run.
+///
+/// `Unsorted` keeps words in the order they first appear in the input, since
+/// words are tallied into an insertion-ordered map. Words merged by
+/// `fuzzy_merge` stay where their most frequent spelling first appears.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
 pub enum Sort {
     #[default]
@@ -59,11 +181,15 @@ pub enum Sort {
 }
 
 impl Sort {
-    /// Sorts the `tally` field in place if a sort order other than `Unsorted` is provided.
+    /// Sorts the `tally` field in place by count, then word, if a sort order other than `Unsorted` is provided.
     pub fn apply(&self, w: &mut WordTally) {
         match self {
-            Self::Desc => w.tally.sort_unstable_by_key(|&(_, count)| Reverse(count)),
-            Self::Asc => w.tally.sort_unstable_by_key(|&(_, count)| count),
+            Self::Desc => w
+                .tally
+                .sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b))),
+            Self::Asc => w
+                .tally
+                .sort_unstable_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(a.cmp(b))),
             Self::Unsorted => (),
         }
     }
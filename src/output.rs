@@ -1,4 +1,5 @@
-use anyhow::{Context, Result};
+use crate::exit_code::CantCreate;
+use anyhow::Result;
 use std::fs::File;
 use std::io::{self, ErrorKind::BrokenPipe, LineWriter, Write};
 use std::path::{Path, PathBuf};
@@ -14,10 +15,10 @@ pub struct Output {
 impl Output {
     /// Creates an `Output` that writes to a file with error context.
     pub fn file(path: PathBuf) -> Result<Self> {
-        let file = File::create(&path)
-            .map(|file| Box::new(LineWriter::new(file)) as Writer)
-            .with_context(|| format!("Failed to create file: {:?}", path))?;
-        Ok(Self { writer: file })
+        let file = File::create(&path).map_err(|source| CantCreate { path, source })?;
+        Ok(Self {
+            writer: Box::new(LineWriter::new(file)),
+        })
     }
 
     /// Creates an `Output` that writes to stdout.
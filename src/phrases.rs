@@ -0,0 +1,118 @@
+use crate::Options;
+use std::collections::{HashMap, VecDeque};
+
+/// Multi-word expressions that are tallied as a single entry instead of their parts.
+///
+/// Phrases are matched against the stream of words, so punctuation and line
+/// breaks between the words of a phrase don't prevent a match. Each phrase is
+/// split into words the same way as the input, so it follows the segmenter,
+/// case and possessive folding of the `Options` it's tallied with. When
+/// phrases overlap, the longest phrase starting at the earliest word wins.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct Phrases(pub Vec<Box<str>>);
+
+impl Phrases {
+    /// Constructs `Phrases`, skipping blank ones.
+    pub fn new<I, S>(phrases: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let phrases = phrases
+            .into_iter()
+            .filter_map(|phrase| {
+                let phrase = phrase.as_ref().trim();
+                (!phrase.is_empty()).then(|| Box::from(phrase))
+            })
+            .collect();
+
+        Self(phrases)
+    }
+
+    /// Creates a matcher for words split and normalized with the given `Options`.
+    pub(crate) fn matcher(&self, options: &Options) -> PhraseMatcher {
+        let mut root = Node::default();
+        let mut spans = Vec::new();
+        for phrase in &self.0 {
+            let mut words = Vec::new();
+            options.words(phrase, &mut spans, |word| words.push(word));
+            if words.is_empty() {
+                continue;
+            }
+
+            let node = words.into_iter().fold(&mut root, |node, word| {
+                node.children.entry(word).or_default()
+            });
+            node.terminal = true;
+        }
+
+        PhraseMatcher {
+            root,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+/// A trie node for the next word of a phrase.
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<Box<str>, Self>,
+    terminal: bool,
+}
+
+/// Groups a stream of words into phrases, holding back words that may start one.
+#[derive(Debug)]
+pub(crate) struct PhraseMatcher {
+    root: Node,
+    pending: VecDeque<Box<str>>,
+}
+
+impl PhraseMatcher {
+    /// Adds the next word, emitting any words or phrases that are resolved.
+    pub fn push(&mut self, word: Box<str>, emit: &mut impl FnMut(Box<str>)) {
+        self.pending.push_back(word);
+        self.drain(false, emit);
+    }
+
+    /// Emits every pending word or phrase at the end of a unit, so phrases don't span units.
+    pub fn flush(&mut self, emit: &mut impl FnMut(Box<str>)) {
+        self.drain(true, emit);
+    }
+
+    /// Emits every remaining word or phrase at the end of input.
+    pub fn finish(mut self, emit: &mut impl FnMut(Box<str>)) {
+        self.flush(emit);
+    }
+
+    /// Emits words and phrases until the pending words could still extend a phrase.
+    fn drain(&mut self, at_end: bool, emit: &mut impl FnMut(Box<str>)) {
+        while !self.pending.is_empty() {
+            let mut node = &self.root;
+            let mut longest = 0;
+            let mut matched = 0;
+
+            for word in &self.pending {
+                match node.children.get(word) {
+                    Some(next) => node = next,
+                    None => break,
+                }
+                matched += 1;
+                if node.terminal {
+                    longest = matched;
+                }
+            }
+
+            let may_extend = matched == self.pending.len() && !node.children.is_empty();
+            if may_extend && !at_end {
+                return;
+            }
+
+            if longest > 1 {
+                let phrase: Vec<_> = self.pending.drain(..longest).collect();
+                emit(phrase.join(" ").into_boxed_str());
+            } else if let Some(word) = self.pending.pop_front() {
+                emit(word);
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{Segmenter, Span};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Sentence, word and syllable counts of an input, for scoring how readable it is.
+///
+/// Sentences are segmented along Unicode sentence boundaries within each
+/// paragraph, like `Unit::Sentence`. Only words with letters are counted, and
+/// their syllables are estimated with English spelling rules, so the scores
+/// are only meaningful for English text.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Readability {
+    /// The number of sentences with at least one word.
+    sentences: usize,
+
+    /// The number of words.
+    words: usize,
+
+    /// The estimated number of syllables in all words.
+    syllables: usize,
+
+    /// The number of words with three or more syllables.
+    polysyllables: usize,
+}
+
+impl Readability {
+    /// Adds the words of a sentence.
+    pub(crate) fn add_sentence(
+        &mut self,
+        sentence: &str,
+        segmenter: Segmenter,
+        spans: &mut Vec<Span>,
+    ) {
+        segmenter.spans(sentence, spans);
+        let mut words = 0;
+        for span in spans.iter() {
+            let syllables = syllables(span.word(sentence));
+            if syllables > 0 {
+                words += 1;
+                self.syllables += syllables;
+                self.polysyllables += usize::from(syllables >= 3);
+            }
+        }
+        if words > 0 {
+            self.sentences += 1;
+            self.words += words;
+        }
+    }
+
+    /// Gets the average number of words per sentence, if there are any sentences.
+    pub fn average_sentence_length(&self) -> Option<f64> {
+        (self.sentences > 0).then(|| self.words as f64 / self.sentences as f64)
+    }
+
+    /// Gets the Flesch-Kincaid grade level, if there are any sentences.
+    pub fn flesch_kincaid_grade(&self) -> Option<f64> {
+        let words_per_sentence = self.average_sentence_length()?;
+        let syllables_per_word = self.syllables as f64 / self.words as f64;
+
+        Some(0.39f64.mul_add(words_per_sentence, 11.8 * syllables_per_word) - 15.59)
+    }
+
+    /// Gets the SMOG grade, if there are any sentences.
+    ///
+    /// SMOG is defined for samples of 30 sentences, so it's scaled to that.
+    pub fn smog(&self) -> Option<f64> {
+        (self.sentences > 0).then(|| {
+            let polysyllables = self.polysyllables as f64 * 30.0 / self.sentences as f64;
+            1.043f64.mul_add(polysyllables.sqrt(), 3.1291)
+        })
+    }
+
+    /// Gets the `sentences` field.
+    pub const fn sentences(&self) -> usize {
+        self.sentences
+    }
+
+    /// Gets the `words` field.
+    pub const fn words(&self) -> usize {
+        self.words
+    }
+
+    /// Gets the `syllables` field.
+    pub const fn syllables(&self) -> usize {
+        self.syllables
+    }
+
+    /// Gets the `polysyllables` field.
+    pub const fn polysyllables(&self) -> usize {
+        self.polysyllables
+    }
+}
+
+/// Estimates the syllables in a word by its groups of vowels, or returns 0 if it has no letters.
+///
+/// A silent final `e`, and an `-ed` or `-es` ending that doesn't add a
+/// syllable, aren't counted. Every word with letters has at least one. The
+/// rules follow English spelling, so counts for other languages are rough.
+///
+/// ```
+/// use word_tally::syllables;
+///
+/// assert_eq!(syllables("readability"), 5);
+/// assert_eq!(syllables("tallied"), 2);
+/// assert_eq!(syllables("42"), 0);
+/// ```
+pub fn syllables(word: &str) -> usize {
+    let word: String = word
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if word.is_empty() {
+        return 0;
+    }
+
+    let is_vowel = |ch: char| matches!(ch, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut groups = 0;
+    let mut after_vowel = false;
+    for ch in word.chars() {
+        let vowel = is_vowel(ch);
+        groups += usize::from(vowel && !after_vowel);
+        after_vowel = vowel;
+    }
+
+    let stem = |suffix: &str| {
+        word.strip_suffix(suffix)
+            .and_then(|stem| stem.chars().last())
+    };
+    let silent = match (stem("e"), stem("ed"), stem("es")) {
+        (Some('l'), _, _) => word[..word.len() - 2].ends_with(is_vowel),
+        (Some(before), _, _) => !is_vowel(before),
+        (_, Some(before), _) => !matches!(before, 't' | 'd') && !is_vowel(before),
+        (_, _, Some(before)) => {
+            !matches!(before, 's' | 'x' | 'z' | 'c' | 'g' | 'h') && !is_vowel(before)
+        }
+        _ => false,
+    };
+
+    (groups - usize::from(silent && groups > 1)).max(1)
+}
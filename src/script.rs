@@ -0,0 +1,145 @@
+use core::fmt::{self, Display, Formatter};
+use std::borrow::Cow;
+
+/// The writing system a word is written in.
+///
+/// Scripts are detected from well-known Unicode block ranges rather than the
+/// full Unicode `Script` property, which covers the major scripts without
+/// pulling in property tables.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Thai,
+    Georgian,
+    Hangul,
+    Hiragana,
+    Katakana,
+    Han,
+    /// Words made only of digits, marks and other script-neutral chars.
+    Common,
+    /// Letters from a script without a dedicated variant.
+    Other,
+}
+
+/// The horizontal direction a script is written in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+    Neutral,
+}
+
+impl Script {
+    /// Detects the script of a word from its first script-specific char.
+    pub fn of(word: &str) -> Self {
+        word.chars().find_map(Self::of_char).unwrap_or(Self::Common)
+    }
+
+    /// Gets the direction text in this script is written in.
+    pub const fn direction(&self) -> Direction {
+        match self {
+            Self::Hebrew | Self::Arabic => Direction::Rtl,
+            Self::Common => Direction::Neutral,
+            _ => Direction::Ltr,
+        }
+    }
+
+    /// Detects the script of a letter, or `None` for script-neutral chars.
+    fn of_char(ch: char) -> Option<Self> {
+        if !ch.is_alphabetic() {
+            return None;
+        }
+
+        let script = match ch as u32 {
+            0x41..=0x5A
+            | 0x61..=0x7A
+            | 0xC0..=0xD6
+            | 0xD8..=0xF6
+            | 0xF8..=0x2AF
+            | 0x1E00..=0x1EFF
+            | 0x2C60..=0x2C7F
+            | 0xA720..=0xA7FF
+            | 0xAB30..=0xAB6F
+            | 0xFF21..=0xFF3A
+            | 0xFF41..=0xFF5A => Self::Latin,
+            0x370..=0x3FF | 0x1F00..=0x1FFF => Self::Greek,
+            0x400..=0x52F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => Self::Cyrillic,
+            0x531..=0x58F | 0xFB13..=0xFB17 => Self::Armenian,
+            0x591..=0x5FF | 0xFB1D..=0xFB4F => Self::Hebrew,
+            0x600..=0x6FF | 0x750..=0x77F | 0x8A0..=0x8FF | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => {
+                Self::Arabic
+            }
+            0x900..=0x97F | 0xA8E0..=0xA8FF => Self::Devanagari,
+            0xE00..=0xE7F => Self::Thai,
+            0x10A0..=0x10FF | 0x1C90..=0x1CBF | 0x2D00..=0x2D2F => Self::Georgian,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xA960..=0xA97F | 0xAC00..=0xD7FF => Self::Hangul,
+            0x3041..=0x309F => Self::Hiragana,
+            0x30A0..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9D => Self::Katakana,
+            0x3005 | 0x3007 | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Self::Han,
+            0x20000..=0x3134F => Self::Han,
+            _ => Self::Other,
+        };
+
+        Some(script)
+    }
+}
+
+impl Display for Script {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let script = match self {
+            Self::Latin => "latin",
+            Self::Greek => "greek",
+            Self::Cyrillic => "cyrillic",
+            Self::Armenian => "armenian",
+            Self::Hebrew => "hebrew",
+            Self::Arabic => "arabic",
+            Self::Devanagari => "devanagari",
+            Self::Thai => "thai",
+            Self::Georgian => "georgian",
+            Self::Hangul => "hangul",
+            Self::Hiragana => "hiragana",
+            Self::Katakana => "katakana",
+            Self::Han => "han",
+            Self::Common => "common",
+            Self::Other => "other",
+        };
+
+        f.write_str(script)
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let direction = match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+            Self::Neutral => "neutral",
+        };
+
+        f.write_str(direction)
+    }
+}
+
+/// Removes invisible bidirectional formatting chars, which word segmentation
+/// otherwise attaches to the adjacent word.
+pub fn strip_bidi_controls(word: &str) -> Cow<'_, str> {
+    if word.contains(is_bidi_control) {
+        Cow::Owned(word.chars().filter(|&ch| !is_bidi_control(ch)).collect())
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+/// Whether a char is an explicit bidirectional mark, embedding or isolate.
+const fn is_bidi_control(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
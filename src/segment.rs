@@ -0,0 +1,136 @@
+use crate::Script;
+use clap::ValueEnum;
+use core::fmt::{self, Display, Formatter};
+use core::ops::Range;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The byte offset and length of a word within segmented text.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl Span {
+    /// Gets the byte range of the word.
+    pub const fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.len
+    }
+
+    /// Gets the word from the text it was segmented from.
+    pub fn word<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.range()]
+    }
+}
+
+/// Segments text into words, writing their spans into a reusable buffer.
+///
+/// The buffer is cleared first, so reusing one buffer across calls avoids
+/// allocating for each word or each call once it has grown to fit.
+pub fn word_spans(text: &str, spans: &mut Vec<Span>) {
+    spans.clear();
+    spans.extend(text.unicode_word_indices().map(|(offset, word)| Span {
+        offset,
+        len: word.len(),
+    }));
+}
+
+/// How text is segmented into words.
+///
+/// `Unicode` segments along Unicode word boundaries, skipping punctuation and
+/// whitespace. `Whitespace` splits on whitespace alone, keeping punctuation as
+/// part of words, for simple or byte-for-byte predictable tallies.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
+pub enum Segmenter {
+    #[default]
+    Unicode,
+    Whitespace,
+}
+
+impl Segmenter {
+    /// Segments text into words, writing their spans into a reusable buffer like `word_spans`.
+    pub fn spans(self, text: &str, spans: &mut Vec<Span>) {
+        match self {
+            Self::Unicode => word_spans(text, spans),
+            Self::Whitespace => whitespace_spans(text, spans),
+        }
+    }
+}
+
+impl Display for Segmenter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let segmenter = match self {
+            Self::Unicode => "unicode",
+            Self::Whitespace => "whitespace",
+        };
+
+        f.write_str(segmenter)
+    }
+}
+
+/// Splits text into words on whitespace, writing their spans into a reusable buffer.
+fn whitespace_spans(text: &str, spans: &mut Vec<Span>) {
+    spans.clear();
+    let mut start = None;
+    for (offset, ch) in text.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (true, Some(word_start)) => {
+                spans.push(Span {
+                    offset: word_start,
+                    len: offset - word_start,
+                });
+                start = None;
+            }
+            (false, None) => start = Some(offset),
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        spans.push(Span {
+            offset: word_start,
+            len: text.len() - word_start,
+        });
+    }
+}
+
+/// The kind of a word, after the word types of Unicode word break rule statuses.
+///
+/// `Letter` words have letters, possibly mixed with digits, and `Number` words
+/// have digits without letters. `Kana` words are hiragana or katakana and
+/// `Ideo` words are ideographic.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum WordType {
+    Letter,
+    Number,
+    Kana,
+    Ideo,
+}
+
+impl WordType {
+    /// Classifies a word by its `Script`, or as a number if it has digits and no letters.
+    pub fn of(word: &str) -> Self {
+        match Script::of(word) {
+            Script::Han => Self::Ideo,
+            Script::Hiragana | Script::Katakana => Self::Kana,
+            Script::Common if word.chars().any(char::is_numeric) => Self::Number,
+            _ => Self::Letter,
+        }
+    }
+}
+
+impl Display for WordType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let word_type = match self {
+            Self::Letter => "letter",
+            Self::Number => "number",
+            Self::Kana => "kana",
+            Self::Ideo => "ideo",
+        };
+
+        f.write_str(word_type)
+    }
+}
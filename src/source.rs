@@ -0,0 +1,354 @@
+use crate::error::PathError;
+use crate::WordTallyError;
+use core::fmt::{self, Display, Formatter};
+use std::fs::{File, Metadata};
+use std::io::{
+    self,
+    ErrorKind::{InvalidData, InvalidInput},
+    Read, Seek, SeekFrom,
+};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::slice;
+
+/// Where input to tally is read from.
+///
+/// A `Source` is usually parsed from a command-line argument, where `"-"`
+/// stands for stdin and anything else is a file path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// A file at a path.
+    Path(PathBuf),
+
+    /// Standard input.
+    Stdin,
+
+    /// Bytes already in memory.
+    Bytes(Box<[u8]>),
+
+    /// Sources read one after another, each opened once the one before it is
+    /// read and ending on a line break.
+    List(Box<[Self]>),
+}
+
+impl Source {
+    /// Opens the source for reading.
+    pub fn open(&self) -> Result<Box<dyn Read + '_>, WordTallyError> {
+        Ok(self.reader()?)
+    }
+
+    /// Opens the source for reading only the words that start within a byte `range`.
+    ///
+    /// Each end of the range snaps to a cut point: a word cut off at the start
+    /// of the range is skipped and a word cut off at the end is read to its end.
+    /// Adjacent ranges therefore tally every word exactly once, so a large input
+    /// can be sampled or sharded by byte offsets. The end of a word is only
+    /// looked for within 128 bytes of a range end, so text without spaces, like
+    /// Chinese or Japanese, is cut at the next UTF-8 char boundary instead.
+    ///
+    /// Offsets are in bytes of UTF-8 input, so input in other encodings should
+    /// be transcoded first.
+    pub fn open_range(&self, range: Range<u64>) -> Result<Box<dyn Read + '_>, WordTallyError> {
+        let before = range.start.saturating_sub(1);
+        let input = match self {
+            Self::Path(path) if before > 0 => {
+                let mut file = File::open(path).map_err(|err| PathError::wrap(path, err))?;
+                file.seek(SeekFrom::Start(before))?;
+                Box::new(file)
+            }
+            _ => {
+                let mut reader = self.reader()?;
+                io::copy(&mut reader.by_ref().take(before), &mut io::sink())?;
+                reader
+            }
+        };
+
+        let mut byte_range = ByteRange {
+            input,
+            buffer: Vec::new(),
+            position: before,
+            last: None,
+            range_end: range.end.max(range.start),
+            end: None,
+        };
+        if range.start > 0 {
+            byte_range.peek(1)?;
+            byte_range.consume(1);
+        }
+        let start = byte_range.snap(range.start)?;
+        if byte_range.range_end <= start {
+            let end = byte_range.snap(byte_range.range_end)?;
+            byte_range.end = Some(end.max(start));
+        }
+        byte_range.consume(usize::try_from(start - range.start).unwrap_or(usize::MAX));
+
+        Ok(Box::new(byte_range))
+    }
+
+    /// Opens a reader for the source.
+    fn reader(&self) -> io::Result<Box<dyn Read + '_>> {
+        match self {
+            Self::Path(path) => Ok(Box::new(
+                File::open(path).map_err(|err| PathError::wrap(path, err))?,
+            )),
+            Self::Stdin => Ok(Box::new(io::stdin())),
+            Self::Bytes(bytes) => Ok(Box::new(&bytes[..])),
+            Self::List(sources) => Ok(Box::new(ListReader {
+                sources: sources.iter(),
+                current: None,
+            })),
+        }
+    }
+
+    /// Finds the source that a byte `offset` into the input falls in, and the offset within it.
+    ///
+    /// For a `List`, every source before the one found needs a known `size`,
+    /// since each is followed by a line break in the combined input.
+    pub fn locate(&self, offset: u64) -> Option<(&Self, u64)> {
+        match self {
+            Self::List(sources) => {
+                let mut start = 0;
+                for source in sources.iter() {
+                    let end = start + source.size()? + 1;
+                    if offset < end {
+                        return source.locate(offset - start);
+                    }
+                    start = end;
+                }
+
+                None
+            }
+            source => Some((source, offset)),
+        }
+    }
+
+    /// Attaches the path of a file to an error reading it.
+    fn describe(&self, err: io::Error) -> io::Error {
+        match self {
+            Self::Path(path) => PathError::wrap(path, err),
+            _ => err,
+        }
+    }
+
+    /// Reads a `List` of sources from NUL-delimited paths, or newline-delimited
+    /// paths if there are no NULs, like a `find -print0` manifest.
+    ///
+    /// The manifest is read whole, so it's a `TooLarge` error if it's longer
+    /// than `max_buffer` bytes.
+    pub fn read_list<T: Read>(input: T, max_buffer: Option<usize>) -> Result<Self, WordTallyError> {
+        let limit = max_buffer.unwrap_or(usize::MAX);
+        let most = u64::try_from(limit).unwrap_or(u64::MAX).saturating_add(1);
+        let mut manifest = Vec::new();
+        input.take(most).read_to_end(&mut manifest)?;
+        if manifest.len() > limit {
+            return Err(WordTallyError::too_large(0, limit));
+        }
+        let manifest =
+            String::from_utf8(manifest).map_err(|err| io::Error::new(InvalidData, err))?;
+        let separator = if manifest.contains('\0') { '\0' } else { '\n' };
+
+        manifest
+            .split(separator)
+            .filter(|path| !path.is_empty())
+            .map(Self::try_from)
+            .collect::<Result<_, _>>()
+            .map(Self::List)
+    }
+
+    /// Gets the size of the source in bytes, if it's known without reading it.
+    ///
+    /// Paths to named pipes and devices stream like stdin, so have no size.
+    pub fn size(&self) -> Option<u64> {
+        match self {
+            Self::Path(path) => path
+                .metadata()
+                .ok()
+                .filter(Metadata::is_file)
+                .map(|metadata| metadata.len()),
+            Self::Stdin => None,
+            Self::Bytes(bytes) => Some(bytes.len() as u64),
+            Self::List(sources) => sources.iter().map(Self::size).sum(),
+        }
+    }
+}
+
+/// A reader for a `List` of sources, opening each one only once the one before it is read.
+struct ListReader<'a> {
+    /// Sources not yet opened.
+    sources: slice::Iter<'a, Source>,
+
+    /// The source being read, and its reader.
+    current: Option<(&'a Source, Box<dyn Read + 'a>)>,
+}
+
+impl Read for ListReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            match &mut self.current {
+                Some((source, reader)) => {
+                    let len = reader.read(buf).map_err(|err| source.describe(err))?;
+                    if len > 0 {
+                        return Ok(len);
+                    }
+                    // Each source ends on a line break, closing it before the next is opened.
+                    self.current = None;
+                    buf[0] = b'\n';
+                    return Ok(1);
+                }
+                None => match self.sources.next() {
+                    Some(source) => {
+                        let reader = source.reader().map_err(|err| source.describe(err))?;
+                        self.current = Some((source, reader));
+                    }
+                    None => return Ok(0),
+                },
+            }
+        }
+    }
+}
+
+/// Most bytes past a range end that are searched for the end of a word.
+const RANGE_LOOKAHEAD: usize = 128;
+
+/// A reader for the words that start within a byte range of an input.
+struct ByteRange<R> {
+    /// Input positioned after `buffer`.
+    input: R,
+
+    /// Bytes read ahead from the input to find a cut point, but not yet consumed.
+    buffer: Vec<u8>,
+
+    /// Offset in the input of the next byte to consume.
+    position: u64,
+
+    /// The last byte consumed, just before `position`.
+    last: Option<u8>,
+
+    /// End of the range, before it's snapped to a cut point.
+    range_end: u64,
+
+    /// End of the range snapped to a cut point, once it's been reached.
+    end: Option<u64>,
+}
+
+impl<R: Read> ByteRange<R> {
+    /// Reads ahead until `len` bytes are buffered or the input ends, returning the buffer.
+    fn peek(&mut self, len: usize) -> io::Result<&[u8]> {
+        let mut chunk = [0; RANGE_LOOKAHEAD];
+        while self.buffer.len() < len {
+            let read = self.input.read(&mut chunk)?;
+            if read == 0 {
+                break;
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+
+        Ok(&self.buffer)
+    }
+
+    /// Consumes up to `len` buffered bytes.
+    fn consume(&mut self, len: usize) {
+        let len = len.min(self.buffer.len());
+        if len > 0 {
+            self.last = Some(self.buffer[len - 1]);
+            self.buffer.drain(..len);
+            self.position += len as u64;
+        }
+    }
+
+    /// Finds the cut point for a range end at `offset`, which is at or just past `position`.
+    ///
+    /// An end between words is its own cut point. An end within a word
+    /// snaps forward to the whitespace or end of input after it, or to the
+    /// next UTF-8 char boundary if neither is within `RANGE_LOOKAHEAD` bytes.
+    fn snap(&mut self, offset: u64) -> io::Result<u64> {
+        let ahead = usize::try_from(offset - self.position).unwrap_or(usize::MAX);
+        let before = match ahead {
+            0 => self.last,
+            ahead => self.peek(ahead)?.get(ahead - 1).copied(),
+        };
+        if before.is_none_or(|byte| byte.is_ascii_whitespace()) {
+            return Ok(offset);
+        }
+
+        let buffered = self.peek(ahead + RANGE_LOOKAHEAD)?;
+        let window = buffered
+            .get(ahead..buffered.len().min(ahead + RANGE_LOOKAHEAD))
+            .unwrap_or_default();
+        let cut = window
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .or_else(|| (window.len() < RANGE_LOOKAHEAD).then_some(window.len()))
+            .or_else(|| window.iter().position(|byte| byte & 0xC0 != 0x80))
+            .unwrap_or(window.len());
+
+        Ok(offset + cut as u64)
+    }
+}
+
+impl<R: Read> Read for ByteRange<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let end = match self.end {
+            Some(end) => end,
+            None if self.position >= self.range_end => {
+                let end = self.snap(self.range_end)?;
+                self.end = Some(end);
+                end
+            }
+            None => self.range_end,
+        };
+        let limit = usize::try_from(end.saturating_sub(self.position)).unwrap_or(usize::MAX);
+        let len = limit.min(buf.len());
+        if len == 0 {
+            return Ok(0);
+        }
+
+        if !self.buffer.is_empty() {
+            let len = len.min(self.buffer.len());
+            buf[..len].copy_from_slice(&self.buffer[..len]);
+            self.consume(len);
+            return Ok(len);
+        }
+
+        let len = self.input.read(&mut buf[..len])?;
+        if len > 0 {
+            self.last = Some(buf[len - 1]);
+            self.position += len as u64;
+        }
+
+        Ok(len)
+    }
+}
+
+/// A `Source` is parsed from `"-"` for stdin or otherwise a file path.
+impl TryFrom<&str> for Source {
+    type Error = WordTallyError;
+
+    fn try_from(path: &str) -> Result<Self, Self::Error> {
+        match path {
+            "" => Err(io::Error::new(InvalidInput, "Empty source path").into()),
+            "-" => Ok(Self::Stdin),
+            path => Ok(Self::Path(PathBuf::from(path))),
+        }
+    }
+}
+
+/// A `Source` displays as its file name, `"-"` for stdin, `"bytes"` for bytes
+/// or the number of sources in a list.
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => match path.file_name() {
+                Some(name) => write!(f, "{}", name.to_string_lossy()),
+                None => write!(f, "{}", path.display()),
+            },
+            Self::Stdin => f.write_str("-"),
+            Self::Bytes(_) => f.write_str("bytes"),
+            Self::List(sources) => write!(f, "{} sources", sources.len()),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Helpers for concise tests against tallies, enabled with the `testing` feature.
+//!
+//! ```
+//! use word_tally::{assert_tally_eq, testing};
+//!
+//! let tally = testing::tally("The wombat and the bat");
+//! assert_tally_eq!(tally, [("the", 2), ("and", 1), ("bat", 1), ("wombat", 1)]);
+//! ```
+use crate::{Filters, Options, WordTally};
+
+/// Tallies text with default `Options` and `Filters`.
+pub fn tally(text: &str) -> WordTally {
+    tally_with(text, Options::default(), Filters::default())
+}
+
+/// Tallies text with the given `Options` and `Filters`.
+pub fn tally_with(text: &str, options: Options, filters: Filters) -> WordTally {
+    WordTally::new(text.as_bytes(), options, filters)
+}
+
+/// Gets a tally's words and counts as borrowed pairs, for comparing to literals.
+pub fn pairs(tally: &WordTally) -> Vec<(&str, usize)> {
+    tally
+        .tally()
+        .iter()
+        .map(|(word, count)| (word.as_ref(), *count))
+        .collect()
+}
+
+/// Asserts that a tally has exactly the given words and counts, in order.
+///
+/// The expected tally is anything that derefs to a slice of `(&str, usize)`
+/// pairs, like an array literal.
+#[macro_export]
+macro_rules! assert_tally_eq {
+    ($tally:expr_2021, $expected:expr_2021 $(,)?) => {{
+        let expected: &[(&str, usize)] = &$expected;
+        assert_eq!($crate::testing::pairs(&$tally), expected);
+    }};
+}
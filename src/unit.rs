@@ -0,0 +1,93 @@
+use clap::ValueEnum;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The span of text a word is counted once per.
+///
+/// `Word` counts every occurrence of a word. `Sentence` and `Paragraph`
+/// instead count the number of sentences or paragraphs a word appears in.
+/// Paragraphs are separated by blank lines, and sentences are segmented along
+/// Unicode sentence boundaries within a paragraph, so a sentence can be
+/// wrapped across lines. Phrases don't span sentences or paragraphs.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
+pub enum Unit {
+    #[default]
+    Word,
+    Sentence,
+    Paragraph,
+}
+
+impl Unit {
+    /// Constructs `Units` to split lines of text into this unit.
+    pub(crate) const fn units(self) -> Units {
+        Units {
+            unit: self,
+            index: 0,
+            paragraph: String::new(),
+        }
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let unit = match self {
+            Self::Word => "word",
+            Self::Sentence => "sentence",
+            Self::Paragraph => "paragraph",
+        };
+
+        f.write_str(unit)
+    }
+}
+
+/// Splits lines of text into units, numbering each one.
+///
+/// Lines are passed on as they're pushed, except for sentences, which are
+/// buffered until the end of their paragraph so they can be segmented across
+/// line breaks.
+#[derive(Debug)]
+pub(crate) struct Units {
+    /// The unit text is split into.
+    unit: Unit,
+
+    /// Index of the current unit.
+    index: usize,
+
+    /// Lines of the current paragraph, joined with spaces, when splitting into sentences.
+    paragraph: String,
+}
+
+impl Units {
+    /// Passes the text of a line that's ready, and the index of its unit, to `each`.
+    pub fn push_line(&mut self, line: &str, mut each: impl FnMut(&str, usize)) {
+        let is_blank = line.trim().is_empty();
+        match self.unit {
+            Unit::Word => each(line, self.index),
+            Unit::Sentence if is_blank => self.finish(each),
+            Unit::Sentence => {
+                self.paragraph.push_str(line.trim_end());
+                self.paragraph.push(' ');
+            }
+            Unit::Paragraph if is_blank => self.index += 1,
+            Unit::Paragraph => each(line, self.index),
+        }
+    }
+
+    /// Gets the number of bytes buffered for the current paragraph.
+    pub const fn buffered(&self) -> usize {
+        self.paragraph.len()
+    }
+
+    /// Passes each buffered sentence, and its index, to `each` at the end of a paragraph or input.
+    pub fn finish(&mut self, mut each: impl FnMut(&str, usize)) {
+        for sentence in self.paragraph.unicode_sentences() {
+            each(sentence, self.index);
+            self.index += 1;
+        }
+        self.paragraph.clear();
+    }
+}
@@ -1,24 +1,37 @@
+use crate::memory;
 use crate::output::Output;
 use anyhow::Result;
-use word_tally::WordTally;
+use word_tally::{Source, WordTally};
+
+/// The level of detail for stderr output.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+    Debug,
+}
 
 pub struct Verbose<'a> {
     output: Output,
+    verbosity: Verbosity,
     tally: &'a WordTally,
     delimiter: &'a str,
-    source: &'a str,
+    source: &'a Source,
 }
 
 impl<'a> Verbose<'a> {
     /// Constructs a new `Verbose` logger with the given output.
     pub const fn new(
         output: Output,
+        verbosity: Verbosity,
         tally: &'a WordTally,
         delimiter: &'a str,
-        source: &'a str,
+        source: &'a Source,
     ) -> Self {
         Self {
             output,
+            verbosity,
             tally,
             delimiter,
             source,
@@ -38,6 +51,10 @@ impl<'a> Verbose<'a> {
     /// Log word tally details.
     fn log_details(&mut self) -> Result<()> {
         self.write_entry("source", self.source)?;
+        if self.verbosity >= Verbosity::Debug {
+            self.write_entry("source-bytes", self.format(self.source.size()))?;
+            self.write_entry("peak-memory-bytes", self.format(memory::peak_rss()))?;
+        }
         self.write_entry("total-words", self.tally.count())?;
         self.write_entry("unique-words", self.tally.uniq_count())?;
         self.write_entry("delimiter", format!("{:?}", self.delimiter))?;
@@ -49,6 +66,11 @@ impl<'a> Verbose<'a> {
     fn log_options(&mut self) -> Result<()> {
         self.write_entry("case", self.tally.options().case)?;
         self.write_entry("order", self.tally.options().sort)?;
+        self.write_entry("fuzzy-merge", self.format(self.tally.options().fuzzy_merge))?;
+        self.write_entry("fold-possessives", self.tally.options().fold_possessives)?;
+        self.write_entry("encoding", self.tally.options().encoding)?;
+        self.write_entry("segmenter", self.tally.options().segmenter)?;
+        self.write_entry("unit", self.tally.options().unit)?;
 
         Ok(())
     }
@@ -56,11 +78,21 @@ impl<'a> Verbose<'a> {
     /// Log word tally filters.
     fn log_filters(&mut self) -> Result<()> {
         self.write_entry("min-chars", self.format(self.tally.filters().min_chars))?;
+        self.write_entry("length-unit", self.tally.filters().length_unit)?;
+        self.write_entry(
+            "min-syllables",
+            self.format(self.tally.filters().min_syllables),
+        )?;
         self.write_entry("min-count", self.format(self.tally.filters().min_count))?;
         self.write_entry(
             "exclude-words",
             self.format(self.tally.filters().exclude.clone()),
         )?;
+        self.write_entry("denoise", self.format(self.tally.filters().denoise))?;
+        self.write_entry(
+            "word-types",
+            self.format(self.tally.filters().word_types.clone()),
+        )?;
 
         Ok(())
     }
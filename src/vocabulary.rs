@@ -0,0 +1,78 @@
+use crate::{Filters, Lexicon, Options, WordTally, WordTallyError};
+use indexmap::IndexSet;
+use std::io::Read;
+
+/// The distinct words in an input, without their counts.
+///
+/// A `Vocabulary` reads words the same way as a `WordTally` but only keeps
+/// each one once, in order of first appearance. Filters that depend on counts,
+/// like `min_count`, and the `fuzzy_merge` option are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vocabulary {
+    /// The distinct words, in order of first appearance unless sorted.
+    words: Box<[Box<str>]>,
+}
+
+impl Vocabulary {
+    /// Constructs a new `Vocabulary`, returning an error if the input can't be read as UTF-8.
+    pub fn try_new<T: Read>(
+        input: T,
+        options: &Options,
+        filters: &Filters,
+        lexicon: &Lexicon,
+    ) -> Result<Self, WordTallyError> {
+        let mut words = IndexSet::new();
+        WordTally::read_words(input, options, lexicon, None, |word, _| {
+            words.insert(word);
+        })?;
+
+        #[cfg(feature = "hunspell")]
+        if let Some(dictionary) = &lexicon.dictionary {
+            words = words
+                .into_iter()
+                .map(|word| {
+                    dictionary
+                        .stem(&word)
+                        .map_or(word, |stem| options.case.normalize(stem))
+                })
+                .collect();
+        }
+
+        let keep = filters.word_filter(options.case);
+        words.retain(|word| keep(word));
+
+        Ok(Self {
+            words: words.into_iter().collect(),
+        })
+    }
+
+    /// Sorts the words alphabetically.
+    pub fn sort(&mut self) {
+        self.words.sort_unstable();
+    }
+
+    /// Reverses the order of the words.
+    pub fn reverse(&mut self) {
+        self.words.reverse();
+    }
+
+    /// Gets the `words` field.
+    pub const fn words(&self) -> &[Box<str>] {
+        &self.words
+    }
+
+    /// Consumes the `words` field.
+    pub fn into_words(self) -> Box<[Box<str>]> {
+        self.words
+    }
+
+    /// Gets the number of distinct words.
+    pub const fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns whether there are no words.
+    pub const fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
@@ -0,0 +1,89 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Chars that are displayed two columns wide, from East Asian Wide and
+/// Fullwidth ranges and emoji blocks.
+const WIDE: [(u32, u32); 17] = [
+    (0x1100, 0x115F),   // Hangul Jamo initial consonants
+    (0x231A, 0x231B),   // Watch and hourglass
+    (0x2E80, 0x303E),   // CJK radicals and punctuation
+    (0x3041, 0x33FF),   // Kana and CJK compatibility
+    (0x3400, 0x4DBF),   // CJK Unified Ideographs Extension A
+    (0x4E00, 0x9FFF),   // CJK Unified Ideographs
+    (0xA000, 0xA4CF),   // Yi
+    (0xAC00, 0xD7A3),   // Hangul syllables
+    (0xF900, 0xFAFF),   // CJK Compatibility Ideographs
+    (0xFE30, 0xFE4F),   // CJK Compatibility Forms
+    (0xFF00, 0xFF60),   // Fullwidth forms
+    (0xFFE0, 0xFFE6),   // Fullwidth signs
+    (0x1F1E6, 0x1F1FF), // Regional indicators
+    (0x1F300, 0x1F64F), // Pictographs and emoticons
+    (0x1F680, 0x1F6FF), // Transport and map symbols
+    (0x1F900, 0x1F9FF), // Supplemental symbols and pictographs
+    (0x20000, 0x3FFFD), // CJK Unified Ideographs Extensions B and beyond
+];
+
+/// Chars that take up no columns on their own, like controls and zero-width spaces.
+const ZERO: [(u32, u32); 5] = [
+    (0x00, 0x1F),
+    (0x7F, 0x9F),
+    (0x200B, 0x200F),
+    (0x2060, 0x2064),
+    (0xFEFF, 0xFEFF),
+];
+
+/// Variation selector requesting emoji presentation, which is displayed wide.
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+/// Gets the number of terminal columns text takes up.
+///
+/// Each grapheme is as wide as its widest char, so combining marks and
+/// zero-width joiners add nothing, and emoji presentation is two columns.
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Truncates text with an ellipsis if it's wider than `max_width` columns.
+pub fn truncate(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let mut room = max_width.saturating_sub(1);
+    let mut truncated: String = text
+        .graphemes(true)
+        .take_while(|grapheme| {
+            let width = grapheme_width(grapheme);
+            let fits = width <= room;
+            room = room.saturating_sub(width);
+            fits
+        })
+        .collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Gets the number of terminal columns a grapheme takes up.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.contains(EMOJI_PRESENTATION) {
+        return 2;
+    }
+
+    grapheme.chars().map(char_width).max().unwrap_or_default()
+}
+
+/// Gets the number of terminal columns a char takes up on its own.
+fn char_width(ch: char) -> usize {
+    let in_ranges = |ranges: &[(u32, u32)]| {
+        ranges
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&u32::from(ch)))
+    };
+
+    if in_ranges(&ZERO) {
+        0
+    } else if in_ranges(&WIDE) {
+        2
+    } else {
+        1
+    }
+}
@@ -0,0 +1,77 @@
+//! Runs the CLI against fixture corpora and compares its output to golden files.
+//!
+//! Each fixture in `tests/files/golden` is tallied in every output mode, and
+//! the output is compared to `<fixture>.<mode>.out`. Set `UPDATE_GOLDEN=1` to
+//! rewrite the golden files after an intended change, then review the diff.
+
+use assert_cmd::Command;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const GOLDEN_DIR: &str = "tests/files/golden";
+
+const FIXTURES: [&str; 5] = ["unicode", "cjk", "rtl", "emoji", "long_line"];
+
+const MODES: [(&str, &[&str]); 6] = [
+    ("default", &[]),
+    ("original_asc", &["--case=original", "--sort=asc"]),
+    ("upper_unsorted", &["--case=upper", "--sort=unsorted"]),
+    ("with_script", &["--with-script", "--delimiter=\\t"]),
+    ("align", &["--align"]),
+    ("group_by_script", &["--group-by=script"]),
+];
+
+fn golden(fixture: &str) {
+    let input = Path::new(GOLDEN_DIR).join(format!("{fixture}.txt"));
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let mut mismatches = Vec::new();
+
+    for (mode, args) in MODES {
+        let output = Command::cargo_bin("word-tally")
+            .unwrap()
+            .args(args)
+            .arg(&input)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{fixture} failed in {mode} mode");
+
+        let path = Path::new(GOLDEN_DIR).join(format!("{fixture}.{mode}.out"));
+        let actual = String::from_utf8(output.stdout).unwrap();
+        if update {
+            fs::write(&path, &actual).unwrap();
+        } else if fs::read_to_string(&path).ok().as_deref() != Some(actual.as_str()) {
+            mismatches.push(path.display().to_string());
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "Output differs from golden files (rerun with UPDATE_GOLDEN=1 to update): {mismatches:?}"
+    );
+}
+
+#[test]
+fn golden_unicode() {
+    golden(FIXTURES[0]);
+}
+
+#[test]
+fn golden_cjk() {
+    golden(FIXTURES[1]);
+}
+
+#[test]
+fn golden_rtl() {
+    golden(FIXTURES[2]);
+}
+
+#[test]
+fn golden_emoji() {
+    golden(FIXTURES[3]);
+}
+
+#[test]
+fn golden_long_line() {
+    golden(FIXTURES[4]);
+}
@@ -1,6 +1,13 @@
+use regex::Regex;
 use std::fs::File;
 use std::hash::{DefaultHasher, Hash, Hasher};
-use word_tally::{Case, ExcludeWords, Filters, MinChars, MinCount, Options, Sort, WordTally};
+use std::io::Read;
+use word_tally::{
+    syllables, word_spans, Baseline, Case, Denoise, Direction, Encoding, ErrorKind, ExcludeWords,
+    Filters, FrozenTally, LengthUnit, Lexicon, MinChars, MinCount, MinSyllables, Options, Phrases,
+    Readability, Script, Segmenter, Sort, Source, Span, Unit, Vocabulary, WordTally,
+    WordTallyError, WordType,
+};
 
 const TEST_WORDS_PATH: &str = "tests/files/words.txt";
 
@@ -18,7 +25,7 @@ fn word_tally(options: Options, filters: Filters) -> WordTally {
 }
 
 fn word_tally_test(case: Case, sort: Sort, filters: Filters, fields: &ExpectedFields<'_>) {
-    let word_tally = word_tally(Options { case, sort }, filters);
+    let word_tally = word_tally(Options::new(case, sort), filters);
     assert_eq!(word_tally.count(), fields.count);
     assert_eq!(word_tally.uniq_count(), fields.uniq_count);
 
@@ -242,7 +249,7 @@ fn equality_and_hashing() {
 
     let tallies: Vec<WordTally> = cases_and_sorts
         .iter()
-        .map(|&(case, sort)| word_tally(Options { case, sort }, Filters::default()))
+        .map(|&(case, sort)| word_tally(Options::new(case, sort), Filters::default()))
         .collect();
 
     for tally in &tallies {
@@ -336,12 +343,63 @@ fn test_min_count_from() {
     assert_eq!(MinCount::from(raw), MinCount(43));
 }
 
+#[test]
+fn test_syllables() {
+    let cases = [
+        ("cat", 1),
+        ("make", 1),
+        ("table", 2),
+        ("jumped", 1),
+        ("wanted", 2),
+        ("boxes", 2),
+        ("Beautiful", 3),
+        ("afternoon", 3),
+        ("42", 0),
+    ];
+    for (word, expected) in cases {
+        assert_eq!(syllables(word), expected, "{word}");
+    }
+}
+
+#[test]
+fn test_min_syllables() {
+    let input = b"a cat sat on the beautiful afternoon table".as_slice();
+    let filters = Filters::default().with_min_syllables(Some(2));
+    let tally = WordTally::new(input, Options::default(), filters);
+
+    let words: Vec<&str> = tally
+        .tally()
+        .iter()
+        .map(|(word, _)| word.as_ref())
+        .collect();
+    assert_eq!(words, vec!["afternoon", "beautiful", "table"]);
+    assert_eq!(MinSyllables::from(2).to_string(), "2");
+}
+
 #[test]
 fn test_words_exclude_from() {
     let words = vec!["beep".to_string(), "boop".to_string()];
     assert_eq!(ExcludeWords::from(words.clone()), ExcludeWords(words));
 }
 
+#[test]
+fn test_exclude_words_parse() {
+    let (exclude, dropped) =
+        ExcludeWords::parse([" beep", "", "boop", "Beep", "boop "], Case::Lower);
+    assert_eq!(
+        exclude,
+        ExcludeWords(vec!["beep".to_string(), "boop".to_string()])
+    );
+    assert_eq!(dropped, 3);
+
+    let (exclude, dropped) = ExcludeWords::parse(["beep", "Beep"], Case::Original);
+    assert_eq!(
+        exclude,
+        ExcludeWords(vec!["beep".to_string(), "Beep".to_string()])
+    );
+    assert_eq!(dropped, 0);
+}
+
 #[test]
 fn test_min_count_graphemes() {
     let tally = WordTally::new(
@@ -357,6 +415,937 @@ fn test_min_count_graphemes() {
     assert_eq!(tally.count(), 0);
 }
 
+#[test]
+fn test_length_units() {
+    // A decomposed `"é"` is one grapheme, two chars and three bytes.
+    let word = "e\u{301}";
+    assert_eq!(LengthUnit::Graphemes.len(word), 1);
+    assert_eq!(LengthUnit::Chars.len(word), 2);
+    assert_eq!(LengthUnit::Bytes.len(word), 3);
+}
+
+#[test]
+fn test_min_chars_in_chars() {
+    let filters = Filters {
+        min_chars: Some(MinChars(2)),
+        ..Filters::default()
+    }
+    .with_length_unit(LengthUnit::Chars);
+    let tally = WordTally::new(&b"e\xCC\x81"[..], Options::default(), filters);
+
+    assert_eq!(tally.count(), 1);
+}
+
+#[test]
+fn test_script_of() {
+    let cases = [
+        ("wombat", Script::Latin, Direction::Ltr),
+        ("שלום", Script::Hebrew, Direction::Rtl),
+        ("مرحبا", Script::Arabic, Direction::Rtl),
+        ("привет", Script::Cyrillic, Direction::Ltr),
+        ("漢字", Script::Han, Direction::Ltr),
+        ("ひらがな", Script::Hiragana, Direction::Ltr),
+        ("123", Script::Common, Direction::Neutral),
+        ("٣abc", Script::Latin, Direction::Ltr),
+    ];
+
+    for (word, script, direction) in cases {
+        assert_eq!(Script::of(word), script, "{word}");
+        assert_eq!(Script::of(word).direction(), direction, "{word}");
+    }
+}
+
+#[test]
+fn test_bidi_marks_stripped() {
+    let input = "שלום\u{200F} עולם \u{2067}שלום\u{2069} world".as_bytes();
+    let tally = WordTally::new(input, Options::default(), Filters::default());
+
+    let expected_tally: Box<[(Box<str>, usize)]> =
+        vec![("שלום".into(), 2), ("world".into(), 1), ("עולם".into(), 1)].into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+
+    let input = "שלום \u{200F} world\u{200E}".as_bytes();
+    let options = Options::default().with_segmenter(Segmenter::Whitespace);
+    let tally = WordTally::new(input, options, Filters::default());
+
+    let expected_tally: Box<[(Box<str>, usize)]> =
+        vec![("world".into(), 1), ("שלום".into(), 1)].into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
+#[test]
+fn test_split_by_script() {
+    let input = "wombat שלום wombat bat שלום שלום".as_bytes();
+    let tally = WordTally::new(input, Options::default(), Filters::default());
+    let groups = tally.split_by_script();
+
+    assert_eq!(
+        groups.keys().copied().collect::<Vec<_>>(),
+        vec![Script::Hebrew, Script::Latin]
+    );
+    assert_eq!(groups[&Script::Hebrew].count(), 3);
+    assert_eq!(groups[&Script::Latin].count(), 3);
+    assert_eq!(groups[&Script::Latin].uniq_count(), 2);
+}
+
+#[test]
+fn test_split_by_pattern() {
+    let input = b"running runs ran jumping jumping".as_slice();
+    let tally = WordTally::new(input, Options::default(), Filters::default());
+    let groups = tally.split_by_pattern(&Regex::new("(ing|s)$").unwrap());
+
+    assert_eq!(
+        groups.keys().map(AsRef::as_ref).collect::<Vec<&str>>(),
+        vec!["ing", "s"]
+    );
+    assert_eq!(groups["ing"].count(), 3);
+    assert_eq!(groups["ing"].uniq_count(), 2);
+    assert_eq!(groups["s"].count(), 1);
+}
+
+#[test]
+fn test_phrases() {
+    let input = "New York is not York. Machine\nlearning in new york, machine code.".as_bytes();
+    let phrases = Phrases::new(["new york", "machine learning", "new york city"]);
+    let lexicon = Lexicon::default().with_phrases(phrases);
+    let options = Options::new(Case::Lower, Sort::Unsorted);
+    let tally = WordTally::with_lexicon(input, options, Filters::default(), &lexicon);
+
+    let expected_tally: Box<[(Box<str>, usize)]> = vec![
+        ("new york".into(), 2),
+        ("is".into(), 1),
+        ("not".into(), 1),
+        ("york".into(), 1),
+        ("machine learning".into(), 1),
+        ("in".into(), 1),
+        ("machine".into(), 1),
+        ("code".into(), 1),
+    ]
+    .into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
+#[test]
+fn test_phrases_longest_match() {
+    let input = b"new york city new york new";
+    let lexicon = Lexicon::default().with_phrases(Phrases::new(["new york", "new york city"]));
+    let options = Options::new(Case::Lower, Sort::Unsorted);
+    let tally = WordTally::with_lexicon(&input[..], options, Filters::default(), &lexicon);
+
+    let expected_tally: Box<[(Box<str>, usize)]> = vec![
+        ("new york city".into(), 1),
+        ("new york".into(), 1),
+        ("new".into(), 1),
+    ]
+    .into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
+#[test]
+fn test_phrases_fold_possessives() {
+    let input = b"New York's mayor left new york";
+    let lexicon = Lexicon::default().with_phrases(Phrases::new(["new york's"]));
+    let options = Options::new(Case::Lower, Sort::Unsorted).with_fold_possessives(true);
+    let tally = WordTally::with_lexicon(&input[..], options, Filters::default(), &lexicon);
+
+    let expected_tally: Box<[(Box<str>, usize)]> = vec![
+        ("new york".into(), 2),
+        ("mayor".into(), 1),
+        ("left".into(), 1),
+    ]
+    .into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
+#[test]
+fn test_phrases_whitespace_segmenter() {
+    let input = b"new york, new york";
+    let lexicon = Lexicon::default().with_phrases(Phrases::new(["new york,"]));
+    let options = Options::new(Case::Lower, Sort::Unsorted).with_segmenter(Segmenter::Whitespace);
+    let tally = WordTally::with_lexicon(&input[..], options, Filters::default(), &lexicon);
+
+    let expected_tally: Box<[(Box<str>, usize)]> = vec![
+        ("new york,".into(), 1),
+        ("new".into(), 1),
+        ("york".into(), 1),
+    ]
+    .into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
+#[test]
+fn test_fold_possessives() {
+    let input = b"wombat's wombat s 's it's";
+    let options = Options::new(Case::Original, Sort::Unsorted).with_fold_possessives(true);
+    let tally = WordTally::new(&input[..], options, Filters::default());
+
+    let expected_tally: Box<[(Box<str>, usize)]> =
+        vec![("wombat".into(), 2), ("s".into(), 2), ("it".into(), 1)].into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
+#[test]
+fn test_word_spans() {
+    let text = "Wombats, café—42!";
+    let mut spans = vec![Span::default(); 8];
+    word_spans(text, &mut spans);
+
+    assert_eq!(
+        spans,
+        vec![
+            Span { offset: 0, len: 7 },
+            Span { offset: 9, len: 5 },
+            Span { offset: 17, len: 2 },
+        ]
+    );
+    let words: Vec<&str> = spans.iter().map(|span| span.word(text)).collect();
+    assert_eq!(words, vec!["Wombats", "café", "42"]);
+}
+
+#[test]
+fn test_whitespace_segmenter() {
+    let text = " Wombats, café—42!\t(bat)\n";
+    let mut spans = vec![Span::default(); 8];
+    Segmenter::Whitespace.spans(text, &mut spans);
+
+    let words: Vec<&str> = spans.iter().map(|span| span.word(text)).collect();
+    assert_eq!(words, vec!["Wombats,", "café—42!", "(bat)"]);
+
+    let options = Options::default().with_segmenter(Segmenter::Whitespace);
+    let tally = WordTally::new(text.as_bytes(), options, Filters::default());
+    assert_eq!(tally.uniq_count(), 3);
+}
+
+#[test]
+fn test_sentence_and_paragraph_units() {
+    let text = "The cat sat. The cat ran\naway from the dog.\n\nA dog barked. The dog slept.\n";
+    let count = |unit: Unit, word: &str| {
+        let options = Options::default().with_unit(unit);
+        let tally = WordTally::new(text.as_bytes(), options, Filters::default());
+        tally
+            .tally()
+            .iter()
+            .find(|(tallied, _)| tallied.as_ref() == word)
+            .map(|&(_, count)| count)
+    };
+
+    assert_eq!(count(Unit::Word, "the"), Some(4));
+    assert_eq!(count(Unit::Sentence, "the"), Some(3));
+    assert_eq!(count(Unit::Paragraph, "the"), Some(2));
+    assert_eq!(count(Unit::Sentence, "away"), Some(1));
+    assert_eq!(count(Unit::Paragraph, "dog"), Some(2));
+}
+
+#[test]
+fn test_phrases_stay_within_a_unit() {
+    let lexicon = Lexicon::default().with_phrases(Phrases::new(["new york"]));
+    let input = b"I left New.\nYork is far. New York is near.".as_slice();
+    let options = Options::default().with_unit(Unit::Sentence);
+    let tally = WordTally::with_lexicon(input, options, Filters::default(), &lexicon);
+
+    let words: Vec<&str> = tally
+        .tally()
+        .iter()
+        .map(|(word, _)| word.as_ref())
+        .collect();
+    assert!(words.contains(&"new"));
+    assert!(words.contains(&"york"));
+    assert!(words.contains(&"new york"));
+}
+
+#[test]
+fn test_readability() {
+    let input = b"The cat sat. The dog\nran.\n\nA beautiful afternoon!".as_slice();
+    let (tally, readability) = WordTally::try_with_readability(
+        input,
+        Options::default(),
+        Filters::default(),
+        &Lexicon::default(),
+    )
+    .unwrap();
+
+    assert_eq!(tally.count(), 9);
+    assert_eq!(readability.sentences(), 3);
+    assert_eq!(readability.words(), 9);
+    assert_eq!(readability.syllables(), 13);
+    assert_eq!(readability.polysyllables(), 2);
+    assert_eq!(readability.average_sentence_length(), Some(3.0));
+    assert!(readability.flesch_kincaid_grade().is_some());
+    assert!(readability.smog().is_some());
+
+    let empty = Readability::default();
+    assert_eq!(empty.average_sentence_length(), None);
+    assert_eq!(empty.flesch_kincaid_grade(), None);
+    assert_eq!(empty.smog(), None);
+}
+
+#[test]
+fn test_unsorted_keeps_first_appearance() {
+    let input = b"c b b a a a\nd b c".as_slice();
+    let options = Options::new(Case::Lower, Sort::Unsorted);
+    let tally = WordTally::new(input, options, Filters::default());
+
+    let words: Vec<&str> = tally
+        .tally()
+        .iter()
+        .map(|(word, _)| word.as_ref())
+        .collect();
+    assert_eq!(words, vec!["c", "b", "a", "d"]);
+}
+
+#[test]
+fn test_crlf_line_endings() {
+    let input = b"wombat\r\nbat\r\nwombat\r\n".as_slice();
+    let tally = WordTally::new(input, Options::default(), Filters::default());
+
+    let expected: Box<[(Box<str>, usize)]> = [("wombat".into(), 2), ("bat".into(), 1)].into();
+    assert_eq!(tally.into_tally(), expected);
+}
+
+#[test]
+fn test_crlf_invalid_utf8_offset() {
+    let input = b"wombat\r\nbat \xFF\r\n";
+    let result = WordTally::try_new(&input[..], Options::default(), Filters::default());
+
+    assert!(matches!(
+        result,
+        Err(WordTallyError::Utf8 { offset: 12, .. })
+    ));
+}
+
+#[test]
+fn test_try_documents() {
+    let input = b"wombat bat\n---\nbat bat\r\n---\r\n".as_slice();
+    let documents = WordTally::try_documents(
+        input,
+        "---",
+        Options::default(),
+        &Filters::default(),
+        &Lexicon::default(),
+    )
+    .unwrap();
+
+    let counts: Vec<usize> = documents.iter().map(WordTally::count).collect();
+    assert_eq!(counts, vec![2, 2]);
+    assert_eq!(documents[1].uniq_count(), 1);
+}
+
+#[test]
+fn test_try_documents_invalid_utf8_offset() {
+    let input = b"wombat\n---\nbat \xFF\n".as_slice();
+    let result = WordTally::try_documents(
+        input,
+        "---",
+        Options::default(),
+        &Filters::default(),
+        &Lexicon::default(),
+    );
+
+    assert!(matches!(
+        result,
+        Err(WordTallyError::Utf8 { offset: 15, .. })
+    ));
+}
+
+#[test]
+fn test_utf16_encodings() {
+    let text = "Wombat café wombat";
+    let le: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let be: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let expected: Box<[(Box<str>, usize)]> = [("wombat".into(), 2), ("café".into(), 1)].into();
+
+    for (encoding, bytes) in [(Encoding::Utf16Le, le), (Encoding::Utf16Be, be)] {
+        let options = Options::default().with_encoding(encoding);
+        let tally = WordTally::try_new(&bytes[..], options, Filters::default()).unwrap();
+        assert_eq!(tally.into_tally(), expected, "{encoding}");
+    }
+}
+
+#[test]
+fn test_utf16_surrogate_pair_across_reads() {
+    // "𝔸 a" with the surrogate pair for "𝔸" split between two reads.
+    let input = [0x35, 0xD8].chain(&[0x38, 0xDD, b' ', 0, b'a', 0][..]);
+    let options = Options::default().with_encoding(Encoding::Utf16Le);
+    let tally = WordTally::try_new(input, options, Filters::default()).unwrap();
+
+    let expected: Box<[(Box<str>, usize)]> = [("a".into(), 1), ("𝔸".into(), 1)].into();
+    assert_eq!(tally.into_tally(), expected);
+}
+
+#[test]
+fn test_byte_order_marks() {
+    let options = Options::default().with_segmenter(Segmenter::Whitespace);
+    let tally = WordTally::new(&b"\xEF\xBB\xBFwombat"[..], options, Filters::default());
+    let expected: Box<[(Box<str>, usize)]> = [("wombat".into(), 1)].into();
+    assert_eq!(tally.into_tally(), expected);
+
+    for bom_input in [&b"\xFF\xFEw\0a\0"[..], &b"\xFE\xFF\0w\0a"[..]] {
+        let tally = WordTally::try_new(bom_input, Options::default(), Filters::default()).unwrap();
+        let expected: Box<[(Box<str>, usize)]> = [("wa".into(), 1)].into();
+        assert_eq!(tally.into_tally(), expected);
+    }
+
+    let options = Options::default().with_encoding(Encoding::Latin1);
+    let tally = WordTally::new(&b"\xFF\xFEab"[..], options, Filters::default());
+    let expected: Box<[(Box<str>, usize)]> = [("ÿþab".into(), 1)].into();
+    assert_eq!(tally.into_tally(), expected);
+}
+
+#[test]
+fn test_byte_order_mark_invalid_utf8_offset() {
+    let input = b"\xEF\xBB\xBFab \xFF".as_slice();
+    let result = WordTally::try_new(input, Options::default(), Filters::default());
+
+    assert!(matches!(
+        result,
+        Err(WordTallyError::Utf8 { offset: 6, .. })
+    ));
+}
+
+#[test]
+fn test_single_byte_encodings() {
+    let input = b"Caf\xE9 \x93na\xEFve\x94 caf\xE9".as_slice();
+    let options = Options::default().with_encoding(Encoding::Windows1252);
+    let tally = WordTally::try_new(input, options, Filters::default()).unwrap();
+    let expected: Box<[(Box<str>, usize)]> = [("café".into(), 2), ("naïve".into(), 1)].into();
+    assert_eq!(tally.into_tally(), expected);
+
+    let options = Options::default().with_encoding(Encoding::Latin1);
+    let tally = WordTally::try_new(input, options, Filters::default()).unwrap();
+    assert_eq!(tally.count(), 3);
+}
+
+#[test]
+fn test_utf16_invalid() {
+    use std::error::Error;
+
+    let options = Options::default().with_encoding(Encoding::Utf16Le);
+    let unpaired = WordTally::try_new(&b"a\0\0\xD8b\0"[..], options, Filters::default());
+    let err = unpaired.unwrap_err();
+    assert!(err.is_utf8());
+    assert!(err.source().is_none());
+    assert!(matches!(
+        &err,
+        WordTallyError::Utf8 { encoding: Encoding::Utf16Le, offset: 2, bytes, .. }
+            if bytes.as_ref() == b"\0\xD8b\0"
+    ));
+    assert_eq!(err.to_string(), "Invalid UTF-16LE at byte 2: 00 d8 62 00");
+
+    let odd = WordTally::try_new(&b"\xFF\xFEa\0b"[..], options, Filters::default());
+    assert!(matches!(odd, Err(WordTallyError::Utf8 { offset: 4, .. })));
+}
+
+#[test]
+fn test_try_new_invalid_utf8() {
+    let input = b"wombat\nbat \xFF\xFEbat\nwombat";
+    let result = WordTally::try_new(&input[..], Options::default(), Filters::default());
+
+    match result {
+        Err(WordTallyError::Utf8 { offset, bytes, .. }) => {
+            assert_eq!(offset, 11);
+            assert_eq!(bytes.as_ref(), b"\xFF\xFEbat\n");
+        }
+        other => panic!("Expected a UTF-8 error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_new_stops_at_invalid_utf8() {
+    let input = b"wombat\nbat \xFF\nwombat";
+    let tally = WordTally::new(&input[..], Options::default(), Filters::default());
+
+    assert_eq!(tally.count(), 1);
+}
+
+#[test]
+fn test_error_kind() {
+    let input = b"bat \xFF".as_slice();
+    let err = WordTally::try_new(input, Options::default(), Filters::default()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Utf8);
+    assert!(err.is_utf8() && !err.is_io());
+
+    let err = Source::try_from("tests/files/missing.txt")
+        .unwrap()
+        .open()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::Io);
+    assert!(err.is_io() && !err.is_utf8());
+    assert!(
+        Baseline::parse("bat,many\nwombat,1\nbat,many\n", Case::Lower)
+            .unwrap_err()
+            .is_io()
+    );
+}
+
+#[test]
+fn test_utf8_error_display() {
+    let input = b"ok \xC3\x28";
+    let err = WordTally::try_new(&input[..], Options::default(), Filters::default()).unwrap_err();
+
+    assert_eq!(err.to_string(), "Invalid UTF-8 at byte 3: c3 28");
+}
+
+#[test]
+fn test_io_error_display() {
+    use std::error::Error;
+
+    let err = Baseline::parse("word,count\nbat,many\n", Case::Lower).unwrap_err();
+
+    assert_eq!(err.to_string(), "Failed to read input");
+    assert!(err
+        .source()
+        .is_some_and(|source| source.to_string() == "Invalid count: bat,many"));
+}
+
+#[test]
+fn test_retain_ranks() {
+    let input = b"c b b a a a d".as_slice();
+    let mut words = WordTally::new(input, Options::default(), Filters::default());
+    words.retain_ranks(2..=3);
+
+    assert_eq!(words.count(), 3);
+    assert_eq!(words.uniq_count(), 2);
+    let expected: Box<[(Box<str>, usize)]> = [("b".into(), 2), ("c".into(), 1)].into();
+    assert_eq!(words.into_tally(), expected);
+}
+
+#[test]
+fn test_suggest_min_count() {
+    let input = "a b c d e f g h i j k k l l m m n n o o p p p q q q r r r r r r r r r r r r r r r r r r r r";
+    let words = WordTally::new(input.as_bytes(), Options::default(), Filters::default());
+    assert_eq!(words.suggest_min_count(), Some(3));
+
+    let flat = WordTally::new(b"a b c".as_slice(), Options::default(), Filters::default());
+    assert_eq!(flat.suggest_min_count(), None);
+}
+
+#[test]
+fn test_reverse() {
+    let input = b"c b b a a a".as_slice();
+    let mut words = WordTally::new(input, Options::default(), Filters::default());
+    words.reverse();
+
+    let expected: Box<[(Box<str>, usize)]> =
+        [("c".into(), 1), ("b".into(), 2), ("a".into(), 3)].into();
+    assert_eq!(words.into_tally(), expected);
+}
+
+#[test]
+fn test_baseline_keyness() {
+    let baseline = Baseline::parse("word,count\nWombat,1\nbat,9\n", Case::Lower).unwrap();
+    assert_eq!(baseline.get("wombat"), 1);
+    assert_eq!(baseline.total(), 10);
+
+    let overused = baseline.keyness("wombat", 2, 3);
+    let underused = baseline.keyness("bat", 1, 3);
+    assert!((overused - 2.571).abs() < 0.001);
+    assert!((underused + 1.154).abs() < 0.001);
+    assert_eq!(baseline.keyness("bat", 9, 10), 0.0);
+}
+
+#[test]
+fn test_baseline_header_after_comments() {
+    let csv = "\n# Reference corpus\n\nword,count\nwombat,1\nbat,9\n";
+    let baseline = Baseline::parse(csv, Case::Lower).unwrap();
+    assert_eq!(baseline.get("wombat"), 1);
+    assert_eq!(baseline.total(), 10);
+}
+
+#[test]
+fn test_baseline_invalid_count() {
+    assert!(Baseline::parse("wombat,1\nbat,many\n", Case::Lower).is_err());
+}
+
+#[test]
+fn test_denoise_ocr() {
+    for noise in ["tb1rn", "c0ntr", "it''s", "a..b"] {
+        assert!(Denoise::Ocr.is_noise(noise), "{noise}");
+    }
+    for word in ["wombat", "l1ke", "2nd", "3RD", "42", "ж1", "it's"] {
+        assert!(!Denoise::Ocr.is_noise(word), "{word}");
+    }
+    assert!(Denoise::Ocr.is_noise("it\u{2019}\u{2019}s"));
+}
+
+#[test]
+fn test_denoise_ocr_keeps_decomposed_words() {
+    // Decomposed (NFD) Vietnamese, with stacked combining marks.
+    let input = "tie\u{302}\u{301}ng vie\u{323}\u{302}t ngu\u{31B}o\u{31B}\u{300}i";
+    let filters = Filters::default().with_denoise(Some(Denoise::Ocr));
+    let words = WordTally::new(input.as_bytes(), Options::default(), filters);
+
+    assert_eq!(words.uniq_count(), 3);
+}
+
+#[test]
+fn test_denoise_filter() {
+    let input = b"tb1rn wombat 2nd".as_slice();
+    let filters = Filters::default().with_denoise(Some(Denoise::Ocr));
+    let words = WordTally::new(input, Options::default(), filters);
+
+    assert_eq!(words.uniq_count(), 2);
+}
+
+#[test]
+fn test_vocabulary() {
+    let input = b"Wombat bat wombat a cat bat".as_slice();
+    let filters = Filters::new(&Some(2), &Some(9), Some(vec!["cat".to_string()]));
+    let mut vocabulary =
+        Vocabulary::try_new(input, &Options::default(), &filters, &Lexicon::default()).unwrap();
+
+    let expected: Box<[Box<str>]> = ["wombat".into(), "bat".into()].into();
+    assert_eq!(vocabulary.words(), expected.as_ref());
+    assert_eq!(vocabulary.len(), 2);
+
+    vocabulary.sort();
+    let expected: Box<[Box<str>]> = ["bat".into(), "wombat".into()].into();
+    assert_eq!(vocabulary.into_words(), expected);
+}
+
+#[test]
+fn test_source_try_from() {
+    assert_eq!(Source::try_from("-").unwrap(), Source::Stdin);
+    assert_eq!(
+        Source::try_from(TEST_WORDS_PATH).unwrap(),
+        Source::Path(TEST_WORDS_PATH.into())
+    );
+    assert!(Source::try_from("").is_err());
+}
+
+#[test]
+fn test_source_open() {
+    let source = Source::try_from(TEST_WORDS_PATH).unwrap();
+    assert_eq!(source.to_string(), "words.txt");
+    assert!(source.size().is_some_and(|size| size > 0));
+
+    let words = WordTally::try_new(
+        source.open().unwrap(),
+        Options::default(),
+        Filters::default(),
+    );
+    assert_eq!(words.unwrap().count(), 45);
+
+    let bytes = Source::Bytes(b"wombat wombat".as_slice().into());
+    assert_eq!(bytes.to_string(), "bytes");
+    assert_eq!(bytes.size(), Some(13));
+    let words = WordTally::try_new(
+        bytes.open().unwrap(),
+        Options::default(),
+        Filters::default(),
+    );
+    assert_eq!(words.unwrap().count(), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_source_open_range() {
+    let text = "alpha beta gamma\ndelta epsilon";
+    let source = Source::Bytes(text.as_bytes().into());
+    let words_in = |start, end| {
+        let reader = source.open_range(start..end).unwrap();
+        let tally = WordTally::new(
+            reader,
+            Options::new(Case::Lower, Sort::Unsorted),
+            Filters::default(),
+        );
+        tally
+            .into_tally()
+            .iter()
+            .map(|(word, _)| word.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(words_in(0, 8), vec!["alpha", "beta"]);
+    assert_eq!(words_in(8, 18), vec!["gamma", "delta"]);
+    assert_eq!(words_in(6, 6), Vec::<String>::new());
+
+    for split in 0..=text.len() as u64 {
+        let mut words = words_in(0, split);
+        words.extend(words_in(split, u64::MAX));
+        assert_eq!(
+            words,
+            vec!["alpha", "beta", "gamma", "delta", "epsilon"],
+            "{split}"
+        );
+    }
+}
+
+#[test]
+fn test_source_open_range_without_spaces() {
+    let text = "天地玄黄宇宙洪荒".repeat(40);
+    let source = Source::Bytes(text.as_bytes().into());
+    let count = |start, end| {
+        let reader = source.open_range(start..end).unwrap();
+        WordTally::new(reader, Options::default(), Filters::default()).count()
+    };
+
+    assert_eq!(count(0, 30), 10);
+    assert_eq!(count(30, 60), 10);
+    assert_eq!(count(31, 61), 10);
+
+    let len = text.len() as u64;
+    let shards: usize = (0..len)
+        .step_by(7)
+        .map(|start| count(start, start + 7))
+        .sum();
+    assert_eq!(shards, 320);
+}
+
+#[test]
+fn test_source_device_has_no_size() {
+    let device = Source::try_from("/dev/null").unwrap();
+    assert_eq!(device.size(), None);
+    let words = WordTally::new(
+        device.open().unwrap(),
+        Options::default(),
+        Filters::default(),
+    );
+    assert_eq!(words.count(), 0);
+}
+
+#[test]
+fn test_max_buffer() {
+    let options = Options::default().with_max_buffer(Some(8));
+    let err = |result: Result<WordTally, WordTallyError>| result.unwrap_err().to_string();
+
+    let long_line = b"bat cat\nwombat wombat\n".as_slice();
+    assert_eq!(
+        err(WordTally::try_new(long_line, options, Filters::default())),
+        "Input at byte 8 exceeds the 8 byte buffer limit"
+    );
+    let short_lines = b"bat cat\nwombat\n".as_slice();
+    assert!(WordTally::try_new(short_lines, options, Filters::default()).is_ok());
+
+    let paragraph = options.with_unit(Unit::Paragraph);
+    assert!(WordTally::try_new(short_lines, paragraph, Filters::default()).is_ok());
+    let sentence = options.with_unit(Unit::Sentence);
+    assert_eq!(
+        err(WordTally::try_new(
+            short_lines,
+            sentence,
+            Filters::default()
+        )),
+        "Input at byte 8 exceeds the 8 byte buffer limit"
+    );
+
+    let documents = b"bat\n%%\nbat\ncat\nbat\n".as_slice();
+    let result = WordTally::try_documents(
+        documents,
+        "%%",
+        options,
+        &Filters::default(),
+        &Lexicon::default(),
+    );
+    assert!(result.is_err_and(|err| err.is_too_large()
+        && err.to_string() == "Input at byte 15 exceeds the 8 byte buffer limit"));
+
+    let manifest = b"a.txt\nb.txt\n".as_slice();
+    assert!(Source::read_list(manifest, Some(12)).is_ok());
+    assert!(Source::read_list(manifest, Some(11)).is_err_and(|err| err.is_too_large()));
+}
+
+#[test]
+fn test_source_read_list() {
+    let manifest = format!("{TEST_WORDS_PATH}\0{TEST_WORDS_PATH}\0");
+    let list = Source::read_list(manifest.as_bytes(), None).unwrap();
+    let path = Source::Path(TEST_WORDS_PATH.into());
+    assert_eq!(list, Source::List([path.clone(), path].into()));
+    assert_eq!(list.to_string(), "2 sources");
+
+    let words = WordTally::try_new(list.open().unwrap(), Options::default(), Filters::default());
+    assert_eq!(words.unwrap().count(), 90);
+
+    let lines = Source::read_list(b"a.txt\nb c.txt\n".as_slice(), None).unwrap();
+    assert_eq!(
+        lines,
+        Source::List([Source::Path("a.txt".into()), Source::Path("b c.txt".into())].into())
+    );
+}
+
+#[test]
+fn test_source_list_separates_sources() {
+    let list = Source::List(
+        [
+            Source::Bytes(b"wom".as_slice().into()),
+            Source::Bytes(b"bat".as_slice().into()),
+        ]
+        .into(),
+    );
+    assert_eq!(list.size(), Some(6));
+
+    let words = WordTally::new(list.open().unwrap(), Options::default(), Filters::default());
+    assert_eq!(words.uniq_count(), 2);
+}
+
+#[test]
+fn test_source_list_opens_lazily() {
+    let list = Source::List(
+        [
+            Source::Bytes(b"wombat".as_slice().into()),
+            Source::Path("tests/files/missing.txt".into()),
+        ]
+        .into(),
+    );
+    let mut reader = list.open().unwrap();
+    let mut first = [0; 7];
+    reader.read_exact(&mut first).unwrap();
+    assert_eq!(&first, b"wombat\n");
+
+    let err = reader.read(&mut first).unwrap_err();
+    assert!(err.to_string().starts_with("tests/files/missing.txt: "));
+}
+
+#[test]
+fn test_source_list_error_paths() {
+    use std::path::Path;
+
+    let missing = Source::List(
+        [
+            Source::Path(TEST_WORDS_PATH.into()),
+            Source::Path("tests/files/missing.txt".into()),
+        ]
+        .into(),
+    );
+    let err = WordTally::try_new(
+        missing.open().unwrap(),
+        Options::default(),
+        Filters::default(),
+    )
+    .unwrap_err();
+    assert!(err.is_io());
+    assert_eq!(
+        err.source_path(),
+        Some(Path::new("tests/files/missing.txt"))
+    );
+    assert_eq!(err.to_string(), "Failed to read tests/files/missing.txt");
+
+    let invalid = Source::List(
+        [
+            Source::Path(TEST_WORDS_PATH.into()),
+            Source::Path("tests/files/invalid_utf8.txt".into()),
+        ]
+        .into(),
+    );
+    let err = WordTally::try_new(
+        invalid.open().unwrap(),
+        Options::default(),
+        Filters::default(),
+    )
+    .unwrap_err()
+    .locate(&invalid);
+    assert_eq!(
+        err.source_path(),
+        Some(Path::new("tests/files/invalid_utf8.txt"))
+    );
+    assert!(matches!(err, WordTallyError::Utf8 { offset: 7, .. }));
+}
+
+#[test]
+fn test_source_locate() {
+    let bytes = |bytes: &[u8]| Source::Bytes(bytes.into());
+    let list = Source::List([bytes(b"wom"), bytes(b"bat")].into());
+    assert_eq!(list.locate(2), Some((&bytes(b"wom"), 2)));
+    assert_eq!(list.locate(4), Some((&bytes(b"bat"), 0)));
+    assert_eq!(list.locate(8), None);
+    assert_eq!(Source::Stdin.locate(8), Some((&Source::Stdin, 8)));
+}
+
+#[test]
+fn test_word_type_of() {
+    assert_eq!(WordType::of("wombat"), WordType::Letter);
+    assert_eq!(WordType::of("abc123"), WordType::Letter);
+    assert_eq!(WordType::of("42"), WordType::Number);
+    assert_eq!(WordType::of("カタカナ"), WordType::Kana);
+    assert_eq!(WordType::of("漢"), WordType::Ideo);
+}
+
+#[test]
+fn test_word_types_filter() {
+    let input = "wombat 42 カタカナ 7".as_bytes();
+    let filters = Filters::default().with_word_types(Some(vec![WordType::Number]));
+    let words = WordTally::new(input, Options::default(), filters);
+
+    assert_eq!(words.count(), 2);
+    assert_eq!(words.uniq_count(), 2);
+}
+
+#[test]
+fn test_frozen_tally() {
+    let frozen = FrozenTally::from(word_tally(Options::default(), Filters::default()));
+
+    assert_eq!(frozen.len(), 5);
+    assert_eq!(frozen.get("c"), Some(15));
+    assert_eq!(frozen.get("123"), Some(9));
+    assert_eq!(frozen.get("e"), None);
+    assert!(frozen.contains("a"));
+    assert!(!frozen.contains("A"));
+}
+
+#[test]
+fn test_frozen_tally_prefixed() {
+    let input = b"wombat womb bat wombat worm";
+    let word_tally = WordTally::new(&input[..], Options::default(), Filters::default());
+    let frozen = FrozenTally::from(word_tally);
+
+    let expected: Box<[(Box<str>, usize)]> =
+        vec![("womb".into(), 1), ("wombat".into(), 2)].into_boxed_slice();
+    assert_eq!(frozen.prefixed("womb"), expected.as_ref());
+    assert!(frozen.prefixed("x").is_empty());
+}
+
+#[test]
+fn test_fuzzy_merge() {
+    let input = b"colour color color colors wombat wombats flower";
+    let options = Options::default().with_fuzzy_merge(Some(1));
+    let tally = WordTally::new(&input[..], options, Filters::default());
+
+    let expected_tally: Box<[(Box<str>, usize)]> = vec![
+        ("color".into(), 4),
+        ("wombat".into(), 2),
+        ("flower".into(), 1),
+    ]
+    .into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+    assert_eq!(tally.count(), 7);
+    assert_eq!(tally.uniq_count(), 3);
+}
+
+#[test]
+fn test_fuzzy_merge_skips_short_words() {
+    let input = b"the cat sat on the mat with a hat and a catalog or catalogs";
+    let options = Options::new(Case::Lower, Sort::Unsorted).with_fuzzy_merge(Some(1));
+    let tally = WordTally::new(&input[..], options, Filters::default());
+
+    assert_eq!(tally.uniq_count(), 11);
+    assert!(tally.tally().contains(&("catalog".into(), 2)));
+}
+
+#[test]
+fn test_fuzzy_merge_skips_long_words() {
+    let long = "a".repeat(10_000);
+    let near = format!("{long}b");
+    let input = format!("{long} {long} {near}");
+    let options = Options::default().with_fuzzy_merge(Some(3));
+    let tally = WordTally::new(input.as_bytes(), options, Filters::default());
+
+    assert_eq!(tally.uniq_count(), 2);
+}
+
+#[test]
+fn test_fuzzy_merge_before_filters() {
+    let input = b"gray grey grey";
+    let options = Options::default().with_fuzzy_merge(Some(1));
+    let filters = Filters {
+        min_count: Some(MinCount(3)),
+        ..Filters::default()
+    };
+    let tally = WordTally::new(&input[..], options, filters);
+
+    let expected_tally: Box<[(Box<str>, usize)]> = vec![("grey".into(), 3)].into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
 #[cfg(feature = "serde")]
 #[test]
 fn test_to_json() {
@@ -390,3 +1379,120 @@ fn test_from_json() {
     let deserialized: WordTally = serde_json::from_str(json).unwrap();
     assert_eq!(deserialized, expected);
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_options_json() {
+    let options = Options::new(Case::Upper, Sort::Unsorted).with_fuzzy_merge(Some(1));
+    let json = serde_json::to_string(&options).unwrap();
+
+    let expected_json = r#"{"case":"upper","sort":"unsorted","fuzzy_merge":1,"fold_possessives":false,"encoding":"utf-8","segmenter":"unicode","unit":"word","max_buffer":null}"#;
+    assert_eq!(json, expected_json);
+    assert_eq!(serde_json::from_str::<Options>(&json).unwrap(), options);
+
+    let without_encoding =
+        r#"{"case":"upper","sort":"unsorted","fuzzy_merge":1,"fold_possessives":false}"#;
+    assert_eq!(
+        serde_json::from_str::<Options>(without_encoding).unwrap(),
+        options
+    );
+
+    let without_fold_possessives = r#"{"case":"upper","sort":"unsorted","fuzzy_merge":1}"#;
+    assert_eq!(
+        serde_json::from_str::<Options>(without_fold_possessives).unwrap(),
+        options
+    );
+}
+
+#[cfg(feature = "hunspell")]
+#[test]
+fn test_with_dictionary() {
+    use std::path::Path;
+    use word_tally::Dictionary;
+
+    let dictionary = Dictionary::open(Path::new("tests/files/hunspell/en")).unwrap();
+    let input = b"Berries walked walking baked baking unlocked wombats wombat unknown";
+    let lexicon = Lexicon::default().with_dictionary(dictionary);
+    let tally = WordTally::with_lexicon(
+        &input[..],
+        Options::new(Case::Lower, Sort::Unsorted),
+        Filters::default(),
+        &lexicon,
+    );
+
+    let expected_tally: Box<[(Box<str>, usize)]> = vec![
+        ("berry".into(), 1),
+        ("walk".into(), 2),
+        ("bake".into(), 2),
+        ("lock".into(), 1),
+        ("wombat".into(), 2),
+        ("unknown".into(), 1),
+    ]
+    .into_boxed_slice();
+    assert_eq!(tally.tally(), expected_tally.as_ref());
+}
+
+#[cfg(feature = "hunspell")]
+#[test]
+fn test_dictionary_open_with_dotted_stem() {
+    use std::fs;
+    use std::path::Path;
+    use word_tally::Dictionary;
+
+    let dir = std::env::temp_dir().join("word-tally-dotted-dictionary");
+    fs::create_dir_all(&dir).unwrap();
+    for extension in ["aff", "dic"] {
+        let from = Path::new("tests/files/hunspell/en").with_extension(extension);
+        fs::copy(from, dir.join(format!("en.US.{extension}"))).unwrap();
+    }
+    let dictionary = Dictionary::open(&dir.join("en.US"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(dictionary.unwrap().stem("walked"), Some("walk"));
+}
+
+#[cfg(feature = "hunspell")]
+#[test]
+fn test_with_dictionary_per_sentence() {
+    use std::path::Path;
+    use word_tally::Dictionary;
+
+    let dictionary = Dictionary::open(Path::new("tests/files/hunspell/en")).unwrap();
+    let input = b"I walked and kept walking. Then I walked home.";
+    let lexicon = Lexicon::default().with_dictionary(dictionary);
+    let options = Options::new(Case::Lower, Sort::Unsorted).with_unit(Unit::Sentence);
+    let tally = WordTally::with_lexicon(&input[..], options, Filters::default(), &lexicon);
+
+    let count = |word: &str| {
+        tally
+            .tally()
+            .iter()
+            .find(|(tallied, _)| tallied.as_ref() == word)
+            .map(|&(_, count)| count)
+    };
+    assert_eq!(count("walk"), Some(2));
+    assert_eq!(count("i"), Some(2));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_assert_tally_eq() {
+    use word_tally::{assert_tally_eq, testing};
+
+    assert_tally_eq!(
+        testing::tally("Wombat bat wombat"),
+        [("wombat", 2), ("bat", 1)]
+    );
+
+    let options = Options::new(Case::Original, Sort::Asc);
+    let tally = testing::tally_with("Wombat bat wombat", options, Filters::default());
+    assert_tally_eq!(tally, [("Wombat", 1), ("bat", 1), ("wombat", 1)]);
+    assert_eq!(testing::pairs(&tally).len(), 3);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+#[should_panic(expected = "assertion `left == right` failed")]
+fn test_assert_tally_eq_mismatch() {
+    word_tally::assert_tally_eq!(word_tally::testing::tally("wombat"), [("bat", 1)]);
+}
@@ -24,7 +24,7 @@ fn verbose_without_input() {
     let assert = word_tally().arg("-v").assert();
     assert
         .success()
-        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nmin-chars none\nmin-count none\nexclude-words none\n")
+        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nfuzzy-merge none\nfold-possessives false\nencoding utf-8\nsegmenter unicode\nunit word\nmin-chars none\nlength-unit graphemes\nmin-syllables none\nmin-count none\nexclude-words none\ndenoise none\nword-types none\n")
         .stdout("");
 }
 
@@ -33,7 +33,7 @@ fn verbose_with_min_chars() {
     let assert = word_tally().arg("-v").arg("--min-chars=42").assert();
     assert
         .success()
-        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nmin-chars 42\nmin-count none\nexclude-words none\n")
+        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nfuzzy-merge none\nfold-possessives false\nencoding utf-8\nsegmenter unicode\nunit word\nmin-chars 42\nlength-unit graphemes\nmin-syllables none\nmin-count none\nexclude-words none\ndenoise none\nword-types none\n")
         .stdout("");
 }
 
@@ -42,7 +42,7 @@ fn verbose_with_min_count() {
     let assert = word_tally().arg("-v").arg("--min-count=42").assert();
     assert
         .success()
-        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nmin-chars none\nmin-count 42\nexclude-words none\n")
+        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nfuzzy-merge none\nfold-possessives false\nencoding utf-8\nsegmenter unicode\nunit word\nmin-chars none\nlength-unit graphemes\nmin-syllables none\nmin-count 42\nexclude-words none\ndenoise none\nword-types none\n")
         .stdout("");
 }
 
@@ -54,7 +54,7 @@ fn verbose_with_exclude() {
         .assert();
     assert
         .success()
-        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nmin-chars none\nmin-count none\nexclude-words wombat,trees\n")
+        .stderr("source -\ntotal-words 0\nunique-words 0\ndelimiter \" \"\ncase lower\norder desc\nfuzzy-merge none\nfold-possessives false\nencoding utf-8\nsegmenter unicode\nunit word\nmin-chars none\nlength-unit graphemes\nmin-syllables none\nmin-count none\nexclude-words wombat,trees\ndenoise none\nword-types none\n")
         .stdout("");
 }
 
@@ -63,7 +63,7 @@ fn verbose_with_input() {
     let assert = word_tally().write_stdin("wombat").arg("-v").assert();
     assert
         .success()
-        .stderr("source -\ntotal-words 1\nunique-words 1\ndelimiter \" \"\ncase lower\norder desc\nmin-chars none\nmin-count none\nexclude-words none\n\n")
+        .stderr("source -\ntotal-words 1\nunique-words 1\ndelimiter \" \"\ncase lower\norder desc\nfuzzy-merge none\nfold-possessives false\nencoding utf-8\nsegmenter unicode\nunit word\nmin-chars none\nlength-unit graphemes\nmin-syllables none\nmin-count none\nexclude-words none\ndenoise none\nword-types none\n\n")
         .stdout("wombat 1\n");
 }
 
@@ -78,6 +78,39 @@ fn output() {
     fs::remove_file("test.txt").unwrap();
 }
 
+#[test]
+fn very_verbose() {
+    let assert = word_tally()
+        .arg("-vv")
+        .arg("tests/files/words.txt")
+        .arg("--output=/dev/null")
+        .assert();
+    assert
+        .success()
+        .stderr(str::starts_with(
+            "source words.txt\nsource-bytes 112\npeak-memory-bytes ",
+        ))
+        .stderr(contains("\ntotal-words 45\n"));
+}
+
+#[test]
+fn quiet_conflicts_with_verbose() {
+    let assert = word_tally().args(["--quiet", "--verbose"]).assert();
+    assert.failure().stderr(contains("cannot be used with"));
+}
+
+#[test]
+fn verbose_output() {
+    let assert = word_tally()
+        .write_stdin("wombat")
+        .arg("--verbose-output=verbose.txt")
+        .assert();
+    assert.success().stdout("wombat 1\n").stderr("");
+    let verbose = fs::read_to_string("verbose.txt").unwrap();
+    assert!(verbose.starts_with("source -\ntotal-words 1\n"));
+    fs::remove_file("verbose.txt").unwrap();
+}
+
 #[test]
 fn delimiter() {
     let assert = word_tally().write_stdin("wombat").arg("-d\t").assert();
@@ -123,12 +156,586 @@ fn sort_asc() {
     assert.success().stdout("ccc 1\nbb 2\na 3\n");
 }
 
+#[test]
+fn length_unit_bytes() {
+    let assert = word_tally()
+        .write_stdin("café cafe")
+        .args(["--min-chars=5", "--length-unit=bytes"])
+        .assert();
+    assert.success().stdout("café 1\n");
+}
+
+#[test]
+fn with_script() {
+    let assert = word_tally()
+        .write_stdin("مرحبا wombat مرحبا 42")
+        .arg("--with-script")
+        .assert();
+    assert
+        .success()
+        .stdout("مرحبا 2 arabic rtl\n42 1 common neutral\nwombat 1 latin ltr\n");
+}
+
+#[test]
+fn with_syllables_and_min_syllables() {
+    let assert = word_tally()
+        .write_stdin("the tallied words 42")
+        .args(["--with-syllables", "--min-syllables=1", "--sort=unsorted"])
+        .assert();
+    assert.success().stdout("the 1 1\ntallied 1 2\nwords 1 1\n");
+}
+
+#[test]
+fn rank_range() {
+    let assert = word_tally()
+        .write_stdin("c b b a a a d")
+        .args(["--rank-range", "2..3"])
+        .assert();
+    assert.success().stdout("b 2\nc 1\n");
+}
+
+#[test]
+fn rank_range_invalid() {
+    let assert = word_tally().args(["--rank-range", "0..3"]).assert();
+    assert
+        .failure()
+        .stderr(contains("invalid rank range: \"0..3\""));
+}
+
+#[test]
+fn baseline() {
+    let assert = word_tally()
+        .write_stdin("wombat wombat bat")
+        .args(["--baseline", "tests/files/baseline.csv"])
+        .assert();
+    assert.success().stdout("wombat 2 2.57\nbat 1 -1.15\n");
+}
+
+#[test]
+fn reverse() {
+    let assert = word_tally()
+        .write_stdin("c b b a a a d")
+        .args(["--reverse", "--rank-range", "..3"])
+        .assert();
+    assert.success().stdout("c 1\nb 2\na 3\n");
+}
+
+#[test]
+fn skip_and_limit() {
+    let assert = word_tally()
+        .write_stdin("c b b a a a d e e e e")
+        .args(["--skip", "1", "--limit", "2"])
+        .assert();
+    assert.success().stdout("a 3\nb 2\n");
+}
+
+#[test]
+fn unique() {
+    let assert = word_tally()
+        .write_stdin("Wombat bat wombat cat bat")
+        .args(["--unique", "--limit=2"])
+        .assert();
+    assert.success().stdout("bat\ncat\n");
+}
+
+#[test]
+fn unique_unsorted_reverse() {
+    let assert = word_tally()
+        .write_stdin("wombat bat wombat cat")
+        .args(["--unique", "--sort=unsorted", "--reverse"])
+        .assert();
+    assert.success().stdout("cat\nbat\nwombat\n");
+}
+
+#[test]
+fn unique_max_word_display() {
+    let assert = word_tally()
+        .write_stdin("supercalifragilistic wombat")
+        .args(["--unique", "--max-word-display=6"])
+        .assert();
+    assert.success().stdout("super…\nwombat\n");
+}
+
+#[test]
+fn unique_conflicts_with_min_count() {
+    let assert = word_tally().args(["--unique", "--min-count=2"]).assert();
+    assert.failure().code(2);
+}
+
+#[test]
+fn doc_separator() {
+    let assert = word_tally()
+        .write_stdin("wombat bat bat\n%%\nwombat\n")
+        .args(["--doc-separator=%%", "--limit=1"])
+        .assert();
+    assert.success().stdout("[1]\nbat 2\n\n[2]\nwombat 1\n");
+}
+
+#[test]
+fn max_buffer() {
+    let assert = word_tally()
+        .write_stdin("bat\nwombat wombat\n")
+        .arg("--max-buffer=8")
+        .assert();
+    assert
+        .code(65)
+        .stderr(contains("Input at byte 4 exceeds the 8 byte buffer limit"));
+}
+
+#[test]
+fn doc_separator_normalizes_each_document() {
+    let assert = word_tally()
+        .write_stdin("a b c d e f g h i j\n%%\nwombat bat\n")
+        .args([
+            "--doc-separator=%%",
+            "--normalize-counts=per-thousand",
+            "--limit=1",
+        ])
+        .assert();
+    assert
+        .success()
+        .stdout("[1]\na 100.00\n\n[2]\nbat 500.00\n");
+}
+
+#[test]
+fn unit_paragraph() {
+    let assert = word_tally()
+        .write_stdin("bat bat\ncat\n\nbat\n")
+        .arg("--unit=paragraph")
+        .assert();
+    assert.success().stdout("bat 2\ncat 1\n");
+}
+
+#[test]
+fn readability() {
+    let assert = word_tally()
+        .write_stdin("The cat sat. The dog ran.")
+        .arg("--readability")
+        .assert();
+    assert
+        .success()
+        .stderr("average-sentence-length 3.00\nflesch-kincaid-grade -2.62\nsmog 3.13\n");
+}
+
+#[test]
+fn encoding_utf16le() {
+    let assert = word_tally()
+        .write_stdin(b"w\0o\0m\0b\0a\0t\0".as_slice())
+        .arg("--encoding=utf-16le")
+        .assert();
+    assert.success().stdout("wombat 1\n");
+}
+
+#[test]
+fn utf16_byte_order_mark() {
+    let assert = word_tally()
+        .write_stdin(b"\xFF\xFEw\0o\0m\0b\0a\0t\0".as_slice())
+        .assert();
+    assert.success().stdout("wombat 1\n");
+}
+
+#[test]
+fn encoding_utf16le_invalid() {
+    let assert = word_tally()
+        .write_stdin(b"w\0o\0\0\xD8".as_slice())
+        .arg("--encoding=utf-16le")
+        .assert();
+    assert
+        .failure()
+        .code(65)
+        .stderr(contains("Invalid UTF-16LE at byte 4: 00 d8"));
+}
+
+#[test]
+fn limit_per_group() {
+    let assert = word_tally()
+        .write_stdin("wombat wombat bat привет")
+        .args(["--group-by=script", "--limit=1"])
+        .assert();
+    assert
+        .success()
+        .stdout("[latin]\nwombat 2\n\n[cyrillic]\nпривет 1\n");
+}
+
+#[test]
+fn suggest_min_count() {
+    let assert = word_tally()
+        .write_stdin("a b c d e e f f g g g h h h h h h h h h h")
+        .arg("--suggest-min-count")
+        .assert();
+    assert
+        .success()
+        .stderr("suggested-min-count 3\n")
+        .stdout(contains("h 10\n"));
+}
+
+#[test]
+fn denoise_ocr() {
+    let assert = word_tally()
+        .write_stdin("tb1rn wombat 2nd c0ntr")
+        .args(["--denoise", "ocr", "--sort", "unsorted"])
+        .assert();
+    assert.success().stdout("wombat 1\n2nd 1\n");
+}
+
+#[test]
+fn word_types() {
+    let assert = word_tally()
+        .write_stdin("wombat 42 カタカナ")
+        .args(["--word-types", "number,kana", "--sort", "unsorted"])
+        .assert();
+    assert.success().stdout("42 1\nカタカナ 1\n");
+}
+
+#[test]
+fn empty_fail() {
+    let assert = word_tally().arg("--empty=fail").assert();
+    assert
+        .code(66)
+        .stdout("")
+        .stderr(contains("No words tallied from -"));
+}
+
+#[test]
+fn empty_fail_simple_exit_code() {
+    let assert = word_tally()
+        .args(["--empty=fail", "--exit-codes=simple"])
+        .assert();
+    assert.code(1);
+}
+
+#[test]
+fn exit_code_missing_file() {
+    let assert = word_tally().arg("tests/files/missing.txt").assert();
+    assert.code(66);
+}
+
+#[test]
+fn exit_code_invalid_utf8() {
+    let assert = word_tally().arg("tests/files/invalid_utf8.txt").assert();
+    assert.code(65);
+}
+
+#[test]
+fn exit_code_invalid_utf8_simple() {
+    let assert = word_tally()
+        .args(["--exit-codes=simple", "tests/files/invalid_utf8.txt"])
+        .assert();
+    assert.code(1);
+}
+
+#[test]
+fn exit_code_output_cant_create() {
+    let assert = word_tally()
+        .write_stdin("wombat")
+        .arg("--output=tests/files/missing/out.txt")
+        .assert();
+    assert.code(73).stderr(contains("Failed to create file"));
+}
+
+#[test]
+fn empty_warn() {
+    let assert = word_tally().arg("--empty=warn").assert();
+    assert
+        .success()
+        .stderr("Warning: No words tallied from -\n");
+}
+
+#[test]
+fn empty_warn_quiet() {
+    let assert = word_tally().args(["--empty=warn", "--quiet"]).assert();
+    assert.success().stderr("");
+}
+
+#[test]
+fn normalize_counts() {
+    let assert = word_tally()
+        .write_stdin("wombat wombat bat")
+        .args(["--normalize-counts=per-thousand", "--precision=1"])
+        .assert();
+    assert.success().stdout("wombat 666.7\nbat 333.3\n");
+}
+
+#[test]
+fn normalize_counts_of_total_before_rank_range() {
+    let assert = word_tally()
+        .write_stdin("wombat wombat bat")
+        .args(["--normalize-counts=per-million", "--rank-range=..1"])
+        .assert();
+    assert.success().stdout("wombat 666666.67\n");
+}
+
+#[test]
+fn max_word_display() {
+    let assert = word_tally()
+        .write_stdin("supercalifragilistic wombat wombat")
+        .args(["--max-word-display=6", "--align"])
+        .assert();
+    assert.success().stdout("wombat 2\nsuper… 1\n");
+}
+
+#[test]
+fn max_word_display_wide() {
+    let assert = word_tally()
+        .write_stdin("カタカナカタカナ")
+        .arg("--max-word-display=5")
+        .assert();
+    assert.success().stdout("カタ… 1\n");
+}
+
+#[test]
+fn align_wide() {
+    let assert = word_tally()
+        .write_stdin("カタカナ カタカナ cafe\u{301} wombat")
+        .args(["--align", "--sort=unsorted"])
+        .assert();
+    assert
+        .success()
+        .stdout("カタカナ 2\ncafe\u{301}     1\nwombat   1\n");
+}
+
+#[test]
+fn align() {
+    let assert = word_tally()
+        .write_stdin("wombat bat bat bat bat bat bat bat bat bat bat")
+        .arg("--align")
+        .assert();
+    assert.success().stdout("bat    10\nwombat  1\n");
+}
+
+#[test]
+fn group_by_script() {
+    let assert = word_tally()
+        .write_stdin("wombat привет wombat 42 bat")
+        .arg("--group-by=script")
+        .assert();
+    assert
+        .success()
+        .stdout("[latin]\nwombat 2\nbat 1\n\n[common]\n42 1\n\n[cyrillic]\nпривет 1\n");
+}
+
+#[test]
+fn top_per() {
+    let assert = word_tally()
+        .write_stdin("apple ant ant bat bee bee bee cat")
+        .args(["--top-per", "^.=1"])
+        .assert();
+    assert
+        .success()
+        .stdout("[b]\nbee 3\n\n[a]\nant 2\n\n[c]\ncat 1\n");
+}
+
+#[test]
+fn top_per_invalid_pattern() {
+    let assert = word_tally().args(["--top-per", "(=1"]).assert();
+    assert.failure().stderr(contains("regex parse error"));
+}
+
+#[test]
+fn phrases() {
+    let assert = word_tally()
+        .write_stdin("New York, new york and York")
+        .arg("--phrases=tests/files/phrases.txt")
+        .assert();
+    assert.success().stdout("new york 2\nand 1\nyork 1\n");
+}
+
+#[test]
+fn fold_possessives() {
+    let assert = word_tally()
+        .write_stdin("The dog's bone and the dog\u{2019}s ball. DOG'S dog")
+        .arg("--fold-possessives")
+        .assert();
+    assert
+        .success()
+        .stdout("dog 4\nthe 2\nand 1\nball 1\nbone 1\n");
+}
+
+#[test]
+fn fuzzy_merge() {
+    let assert = word_tally()
+        .write_stdin("wombat wombats wombat bat")
+        .arg("--fuzzy-merge=1")
+        .assert();
+    assert.success().stdout("wombat 3\nbat 1\n");
+}
+
+#[test]
+fn fuzzy_merge_conflicts_with_unit() {
+    let assert = word_tally()
+        .args(["--fuzzy-merge=1", "--unit=sentence"])
+        .assert();
+    assert.failure().code(2);
+}
+
+#[test]
+fn fuzzy_merge_out_of_range() {
+    for distance in ["0", "4"] {
+        let assert = word_tally()
+            .arg(format!("--fuzzy-merge={distance}"))
+            .assert();
+        assert
+            .code(2)
+            .stderr(contains("expected a distance from 1 to 3"));
+    }
+}
+
 #[test]
 fn no_words() {
     let assert = word_tally().write_stdin("").assert();
     assert.success().stdout("");
 }
 
+#[test]
+fn invalid_utf8() {
+    let assert = word_tally().write_stdin(&b"wombat\n\xFFbat"[..]).assert();
+    assert
+        .failure()
+        .stderr(contains("Invalid UTF-8 at byte 7: ff 62 61 74"));
+}
+
+#[test]
+fn invalid_utf8_names_source() {
+    let assert = word_tally().arg("tests/files/invalid_utf8.txt").assert();
+    assert.failure().stderr(
+        contains("Failed to tally words from invalid_utf8.txt")
+            .and(contains("Invalid UTF-8 at byte 7")),
+    );
+}
+
+#[test]
+fn source_without_file_name() {
+    let assert = word_tally().arg("..").assert();
+    assert
+        .failure()
+        .stderr(contains("Failed to tally words from .."));
+}
+
+#[test]
+fn missing_file_names_source() {
+    let assert = word_tally().arg("tests/files/missing.txt").assert();
+    assert
+        .failure()
+        .stderr(contains("Failed to read from missing.txt"));
+}
+
+#[test]
+fn files0_from() {
+    let assert = word_tally()
+        .write_stdin("tests/files/words.txt\0tests/files/words.txt\0")
+        .args(["--files0-from=-", "--limit=1"])
+        .assert();
+    assert.success().stdout("c 30\n");
+}
+
+#[test]
+fn files0_from_names_missing_source() {
+    let assert = word_tally()
+        .write_stdin("tests/files/words.txt\ntests/files/missing.txt\n")
+        .arg("--files0-from=-")
+        .assert();
+    assert
+        .failure()
+        .code(66)
+        .stderr(contains("Failed to read tests/files/missing.txt").and(contains("No such file")));
+}
+
+#[test]
+fn files0_from_names_source_with_invalid_utf8() {
+    let assert = word_tally()
+        .write_stdin("tests/files/words.txt\0tests/files/invalid_utf8.txt\0")
+        .arg("--files0-from=-")
+        .assert();
+    assert.failure().stderr(
+        contains("Failed to tally words from 2 sources").and(contains(
+            "Invalid UTF-8 at byte 7 of tests/files/invalid_utf8.txt:",
+        )),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn files0_from_more_sources_than_open_file_limit() {
+    let list = "tests/files/words.txt\0".repeat(300);
+    let assert = Command::new("sh")
+        .args([
+            "-c",
+            r#"ulimit -n 64 && exec "$0" --files0-from=- --limit=1"#,
+        ])
+        .arg(assert_cmd::cargo::cargo_bin("word-tally"))
+        .write_stdin(list)
+        .assert();
+    assert.success().stdout("c 4500\n");
+}
+
+#[test]
+fn exclude_drops_empty_and_duplicate_words() {
+    let assert = word_tally()
+        .write_stdin("the a wombat")
+        .arg("--exclude=the,,The,a")
+        .assert();
+    assert
+        .success()
+        .stdout("wombat 1\n")
+        .stderr("Warning: Ignored 2 empty or duplicate exclude words\n");
+}
+
+#[test]
+fn exclude_from() {
+    let assert = word_tally()
+        .write_stdin("The tree that wombat")
+        .args(["--exclude-from=tests/files/exclude.txt", "--exclude=tree"])
+        .assert();
+    assert
+        .success()
+        .stdout("wombat 1\n")
+        .stderr("Warning: Ignored 1 empty or duplicate exclude words\n");
+}
+
+#[test]
+fn byte_range() {
+    let assert = word_tally()
+        .args([
+            "--byte-offset=4",
+            "--byte-limit=10",
+            "tests/files/words.txt",
+        ])
+        .assert();
+    assert.success().stdout("123 2\n");
+}
+
+#[test]
+fn byte_range_stdin() {
+    let assert = word_tally()
+        .write_stdin("alpha beta gamma delta")
+        .args(["--byte-offset=3", "--byte-limit=8"])
+        .assert();
+    assert.success().stdout("beta 1\n");
+}
+
+#[test]
+fn byte_range_without_spaces() {
+    let assert = word_tally()
+        .write_stdin("天地玄黄宇宙洪荒".repeat(100))
+        .args(["--byte-offset=30", "--byte-limit=30", "--sort=unsorted"])
+        .assert();
+    assert
+        .success()
+        .stdout("玄 2\n黄 2\n宇 1\n宙 1\n洪 1\n荒 1\n天 1\n地 1\n");
+}
+
+#[test]
+fn byte_range_needs_utf8() {
+    let assert = word_tally()
+        .write_stdin("w\0o\0m\0b\0a\0t\0")
+        .args(["--byte-limit=4", "--encoding=utf-16le"])
+        .assert();
+    assert
+        .failure()
+        .code(2)
+        .stderr(contains("need UTF-8 input, not utf-16le"));
+}
+
 #[test]
 fn test_discard_words() {
     let input = "The tree that would grow to heaven must send its roots to hell.";
@@ -139,3 +746,87 @@ fn test_discard_words() {
         .success()
         .stdout(contains("tree").and(contains("heaven").not().and(contains("hell").not())));
 }
+
+#[cfg(feature = "hunspell")]
+#[test]
+fn hunspell() {
+    let assert = word_tally()
+        .write_stdin("berries berry walked")
+        .arg("--hunspell=tests/files/hunspell/en")
+        .assert();
+    assert.success().stdout("berry 2\nwalk 1\n");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn options_round_trip() {
+    let assert = word_tally()
+        .write_stdin("Wombat wombat")
+        .args([
+            "--case=original",
+            "--sort=asc",
+            "--options-out=options.json",
+        ])
+        .assert();
+    assert.success().stdout("Wombat 1\nwombat 1\n");
+    let json = fs::read_to_string("options.json").unwrap();
+    assert!(json.contains("\"case\": \"original\""));
+
+    let assert = word_tally()
+        .write_stdin("Wombat wombat")
+        .arg("--options-in=options.json")
+        .assert();
+    assert.success().stdout("Wombat 1\nwombat 1\n");
+    fs::remove_file("options.json").unwrap();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_field() {
+    let assert = word_tally()
+        .write_stdin("{\"meta\":{\"text\":\"wombat bat\"}}\n\n{\"meta\":{}}\n{\"meta\":{\"text\":\"wombat\"}}\n")
+        .arg("--json-field=meta.text")
+        .assert();
+    assert.success().stdout("wombat 2\nbat 1\n");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_field_utf16() {
+    let json = "{\"text\":\"café wombat café\"}\n";
+    let utf16: Vec<u8> = json.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let assert = word_tally()
+        .write_stdin(utf16)
+        .args(["--json-field=text", "--encoding=utf-16le"])
+        .assert();
+    assert.success().stdout("café 2\nwombat 1\n");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_field_encoding_from_options_in() {
+    fs::write(
+        "json_field_options.json",
+        r#"{"case":"lower","sort":"desc","encoding":"latin-1"}"#,
+    )
+    .unwrap();
+    let assert = word_tally()
+        .write_stdin(&b"{\"text\":\"caf\xE9 caf\xE9 wombat\"}\n"[..])
+        .args(["--json-field=text", "--options-in=json_field_options.json"])
+        .assert();
+    fs::remove_file("json_field_options.json").unwrap();
+    assert.success().stdout("café 2\nwombat 1\n");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_field_invalid_json() {
+    let assert = word_tally()
+        .write_stdin("{\"text\":\"wombat\"}\nwombat\n")
+        .arg("--json-field=text")
+        .assert();
+    assert
+        .failure()
+        .code(65)
+        .stderr(contains("Invalid JSON on line 2"));
+}